@@ -0,0 +1,13 @@
+# Source this file from ~/.bashrc, then run `tf` to browse with TermiFind and
+# change to the chosen directory when pressing Enter.
+tf() {
+    local choosedir chosen_path
+    choosedir="$(mktemp -t termifind.XXXXXX)" || return
+    command termifind --choosedir "$choosedir" "$@"
+    if [ -s "$choosedir" ]; then
+        chosen_path="$(cat -- "$choosedir")"
+        [ -d "$chosen_path" ] || chosen_path="$(dirname -- "$chosen_path")"
+        cd -- "$chosen_path" || true
+    fi
+    rm -f -- "$choosedir"
+}
@@ -0,0 +1,15 @@
+# Place this file in ~/.config/fish/functions/tf.fish (or source it from
+# config.fish), then run `tf` to browse with TermiFind and change to the chosen
+# directory when pressing Enter.
+function tf
+    set -l choosedir (mktemp -t termifind.XXXXXX); or return
+    command termifind --choosedir $choosedir $argv
+    if test -s $choosedir
+        set -l chosen_path (cat $choosedir)
+        if not test -d $chosen_path
+            set chosen_path (dirname $chosen_path)
+        end
+        cd $chosen_path
+    end
+    rm -f $choosedir
+end
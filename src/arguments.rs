@@ -0,0 +1,384 @@
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    path::PathBuf,
+};
+
+use termifind::settings::{Settings, SortKey, TruncationOptions};
+
+const CHOOSE_DIRECTORY_ENVIRONMENT_VARIABLE: &str = "TERMIFIND_CHOOSEDIR";
+
+pub const HELP_TEXT: &str = "\
+Usage: termifind [OPTIONS] [--] [PATH]
+
+Browse the directories from the root down to PATH (the current directory by default).
+
+Options:
+      --hidden                  Display hidden files
+      --truncate <MODE>         Truncate file names: outliers, average, constant:N, level:N, fit,
+                                or none
+      --containers <N>          Only print the last N directory containers
+      --sort-by-type            Sort directory items by their item type first
+      --width <COLUMNS>         Lay the path out for a terminal COLUMNS wide
+      --color <WHEN>            Use colors: auto, always, or never
+      --choosedir <FILE>        Write the chosen path to FILE instead of stdout
+      --config <FILE>           Use FILE as the user settings file
+      --print-config-path       Print the settings files that are used and exit
+      --check-config            Validate the settings files and exit
+  -h, --help                    Print this help and exit
+  -V, --version                 Print the version and exit
+";
+
+pub enum ColorOptions {
+    Auto, // Only when the output is a terminal and `NO_COLOR` is not set
+    Always,
+    Never,
+}
+
+pub struct Arguments {
+    pub path_option: Option<PathBuf>,
+    pub choose_directory_path_option: Option<PathBuf>,
+    pub width_option: Option<usize>,
+    pub color_options: ColorOptions,
+    pub settings_path_option: Option<PathBuf>,
+    pub should_print_settings_path: bool,
+    pub should_check_settings: bool,
+    pub should_print_help: bool,
+    pub should_print_version: bool,
+
+    // Override the settings for this run
+    pub should_display_hidden_files: bool,
+    pub truncation_options_option: Option<TruncationOptions>,
+    pub number_of_directory_containers_to_print_option: Option<usize>,
+    pub should_sort_by_item_type_indicator: bool,
+}
+
+impl Arguments {
+    pub fn new() -> Result<Self, String> {
+        Arguments::parse(env::args_os().skip(1))
+    }
+
+    /// Paths are kept as they are, as they don't need to be valid UTF-8.  Everything after `--`
+    /// is a path, even if it starts with `-`.
+    fn parse(mut argument_iterator: impl Iterator<Item = OsString>) -> Result<Self, String> {
+        let mut arguments = Arguments {
+            path_option: None,
+            choose_directory_path_option: env::var_os(CHOOSE_DIRECTORY_ENVIRONMENT_VARIABLE)
+                .filter(|choose_directory_path| !choose_directory_path.is_empty())
+                .map(PathBuf::from),
+            width_option: None,
+            color_options: ColorOptions::Auto,
+            settings_path_option: None,
+            should_print_settings_path: false,
+            should_check_settings: false,
+            should_print_help: false,
+            should_print_version: false,
+            should_display_hidden_files: false,
+            truncation_options_option: None,
+            number_of_directory_containers_to_print_option: None,
+            should_sort_by_item_type_indicator: false,
+        };
+        let mut are_flags_allowed = true;
+
+        while let Some(argument) = argument_iterator.next() {
+            if !are_flags_allowed || !argument.as_encoded_bytes().starts_with(b"-") {
+                arguments.set_path(argument)?;
+                continue;
+            }
+
+            if argument == "--" {
+                are_flags_allowed = false;
+                continue;
+            }
+
+            let (flag, inline_value_option) = Arguments::split_flag(&argument)?;
+
+            match flag.as_str() {
+                "--choosedir" => {
+                    arguments.choose_directory_path_option =
+                        Some(PathBuf::from(Arguments::get_flag_value(
+                            &flag,
+                            inline_value_option,
+                            &mut argument_iterator,
+                        )?))
+                }
+                "--width" => {
+                    arguments.width_option = Some(Arguments::get_positive_number(
+                        &flag,
+                        Arguments::get_flag_text_value(
+                            &flag,
+                            inline_value_option,
+                            &mut argument_iterator,
+                        )?,
+                    )?)
+                }
+                "--containers" => {
+                    arguments.number_of_directory_containers_to_print_option =
+                        Some(Arguments::get_positive_number(
+                            &flag,
+                            Arguments::get_flag_text_value(
+                                &flag,
+                                inline_value_option,
+                                &mut argument_iterator,
+                            )?,
+                        )?)
+                }
+                "--truncate" => {
+                    arguments.truncation_options_option = Some(Arguments::get_truncation_options(
+                        &flag,
+                        &Arguments::get_flag_text_value(
+                            &flag,
+                            inline_value_option,
+                            &mut argument_iterator,
+                        )?,
+                    )?)
+                }
+                "--color" => {
+                    arguments.color_options = match Arguments::get_flag_text_value(
+                        &flag,
+                        inline_value_option,
+                        &mut argument_iterator,
+                    )?
+                    .as_str()
+                    {
+                        "auto" => ColorOptions::Auto,
+                        "always" => ColorOptions::Always,
+                        "never" => ColorOptions::Never,
+                        _ => {
+                            return Err(format!("'{}' must be 'auto', 'always', or 'never'", flag))
+                        }
+                    }
+                }
+                "--config" => {
+                    arguments.settings_path_option = Some(PathBuf::from(Arguments::get_flag_value(
+                        &flag,
+                        inline_value_option,
+                        &mut argument_iterator,
+                    )?))
+                }
+                // Flags that don't take a value
+                _ if inline_value_option.is_some() => {
+                    return Err(format!("'{}' does not take a value", flag))
+                }
+                "--print-config-path" => arguments.should_print_settings_path = true,
+                "--check-config" => arguments.should_check_settings = true,
+                "--hidden" => arguments.should_display_hidden_files = true,
+                "--sort-by-type" => arguments.should_sort_by_item_type_indicator = true,
+                "-h" | "--help" => arguments.should_print_help = true,
+                "-V" | "--version" => arguments.should_print_version = true,
+                _ => return Err(format!("Unrecognized argument '{}'", flag)),
+            }
+        }
+
+        Ok(arguments)
+    }
+
+    fn set_path(&mut self, path: OsString) -> Result<(), String> {
+        if self.path_option.is_some() {
+            return Err(format!(
+                "Unexpected argument '{}'; only one path can be given",
+                path.to_string_lossy()
+            ));
+        }
+
+        self.path_option = Some(PathBuf::from(path));
+
+        Ok(())
+    }
+
+    /// Allows both `--flag value` and `--flag=value`.  Only the value may be invalid UTF-8.
+    fn split_flag(argument: &OsStr) -> Result<(String, Option<OsString>), String> {
+        let argument_bytes = argument.as_encoded_bytes();
+        let (flag_bytes, inline_value_option) = match argument_bytes
+            .iter()
+            .position(|argument_byte| *argument_byte == b'=')
+        {
+            Some(index) if argument_bytes.starts_with(b"--") => (
+                &argument_bytes[..index],
+                Some(Arguments::get_os_string(&argument_bytes[index + 1..])),
+            ),
+            _ => (argument_bytes, None),
+        };
+
+        match std::str::from_utf8(flag_bytes) {
+            Ok(flag) => Ok((String::from(flag), inline_value_option)),
+            Err(_) => Err(format!(
+                "Unrecognized argument '{}'",
+                argument.to_string_lossy()
+            )),
+        }
+    }
+
+    #[cfg(unix)]
+    fn get_os_string(bytes: &[u8]) -> OsString {
+        use std::os::unix::ffi::OsStrExt;
+
+        OsStr::from_bytes(bytes).to_os_string()
+    }
+
+    #[cfg(not(unix))]
+    fn get_os_string(bytes: &[u8]) -> OsString {
+        OsString::from(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    fn get_flag_value(
+        flag: &str,
+        inline_value_option: Option<OsString>,
+        argument_iterator: &mut impl Iterator<Item = OsString>,
+    ) -> Result<OsString, String> {
+        inline_value_option
+            .or_else(|| argument_iterator.next())
+            .ok_or_else(|| format!("'{}' requires a value", flag))
+    }
+
+    /// For the values that aren't paths, which must be valid UTF-8
+    fn get_flag_text_value(
+        flag: &str,
+        inline_value_option: Option<OsString>,
+        argument_iterator: &mut impl Iterator<Item = OsString>,
+    ) -> Result<String, String> {
+        Arguments::get_flag_value(flag, inline_value_option, argument_iterator)?
+            .into_string()
+            .map_err(|_| format!("'{}' must be valid UTF-8", flag))
+    }
+
+    fn get_positive_number(flag: &str, value: String) -> Result<usize, String> {
+        match value.parse::<usize>() {
+            Ok(number) if number > 0 => Ok(number),
+            _ => Err(format!("'{}' must be a positive number", flag)),
+        }
+    }
+
+    fn get_truncation_options(flag: &str, value: &str) -> Result<TruncationOptions, String> {
+        let (mode, number_option) = match value.find(':') {
+            Some(index) => (&value[..index], Some(&value[index + 1..])),
+            None => (value, None),
+        };
+        let get_number = || {
+            number_option
+                .and_then(|number| number.parse::<usize>().ok())
+                .ok_or_else(|| format!("'{}' requires a number, such as '{}:10'", flag, mode))
+        };
+
+        match (mode, number_option) {
+            ("none", None) => Ok(TruncationOptions::NoTruncation),
+            ("outliers", None) => Ok(TruncationOptions::Outliers {
+                should_include_truncated_text_indicator_in_length: true,
+            }),
+            ("average", None) => Ok(TruncationOptions::AverageFileNameLength {
+                should_include_truncated_text_indicator_in_length: true,
+            }),
+            ("fit", None) => Ok(TruncationOptions::HorizontalFit),
+            ("constant", _) => Ok(TruncationOptions::Constant {
+                constant: get_number()?,
+                should_include_truncated_text_indicator_in_length: true,
+            }),
+            ("level", _) => Ok(TruncationOptions::Level {
+                level: get_number()?,
+                should_include_truncated_text_indicator_in_length: true,
+            }),
+            _ => Err(format!(
+                "'{}' must be 'outliers', 'average', 'constant:N', 'level:N', 'fit', or 'none'",
+                flag
+            )),
+        }
+    }
+
+    /// Applies the flags that override individual settings
+    pub fn apply_to_settings(&self, settings: &mut Settings) {
+        let path_container_settings = &mut settings.path_container_settings;
+        let directory_container_settings =
+            &mut path_container_settings.directory_container_settings;
+
+        if self.should_display_hidden_files {
+            directory_container_settings.should_display_hidden_files = true;
+        }
+
+        if let Some(truncation_options) = &self.truncation_options_option {
+            directory_container_settings.truncation_options = truncation_options.clone();
+        }
+
+        if self
+            .number_of_directory_containers_to_print_option
+            .is_some()
+        {
+            path_container_settings.number_of_directory_containers_to_print_option =
+                self.number_of_directory_containers_to_print_option;
+        }
+
+        if self.should_sort_by_item_type_indicator {
+            let sort_key_vec = &mut directory_container_settings.sort_settings.sort_key_vec;
+            sort_key_vec.retain(|sort_key| *sort_key != SortKey::ItemType);
+            sort_key_vec.insert(0, SortKey::ItemType);
+        }
+    }
+}
+
+#[cfg(test)]
+fn parse_arguments(argument_vec: Vec<OsString>) -> Result<Arguments, String> {
+    Arguments::parse(argument_vec.into_iter())
+}
+
+#[cfg(unix)]
+#[test]
+fn parse_paths_that_are_not_valid_utf8() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let invalid_path = OsStr::from_bytes(b"/tmp/\xff").to_os_string();
+    let mut inline_config_argument = OsString::from("--config=");
+    inline_config_argument.push(&invalid_path);
+
+    let arguments = parse_arguments(vec![
+        invalid_path.clone(),
+        inline_config_argument,
+        OsString::from("--choosedir"),
+        invalid_path.clone(),
+    ])
+    .unwrap_or_else(|message| panic!("{}", message));
+
+    assert_eq!(arguments.path_option, Some(PathBuf::from(&invalid_path)));
+    assert_eq!(
+        arguments.settings_path_option,
+        Some(PathBuf::from(&invalid_path))
+    );
+    assert_eq!(
+        arguments.choose_directory_path_option,
+        Some(PathBuf::from(&invalid_path))
+    );
+
+    let mut inline_width_argument = OsString::from("--width=");
+    inline_width_argument.push(&invalid_path);
+    assert_eq!(
+        parse_arguments(vec![inline_width_argument]).err(),
+        Some(String::from("'--width' must be valid UTF-8"))
+    );
+}
+
+#[test]
+fn parse_paths_after_double_dash() {
+    let arguments = parse_arguments(vec![
+        OsString::from("--hidden"),
+        OsString::from("--"),
+        OsString::from("-directory"),
+    ])
+    .unwrap_or_else(|message| panic!("{}", message));
+
+    assert!(arguments.should_display_hidden_files);
+    assert_eq!(arguments.path_option, Some(PathBuf::from("-directory")));
+
+    assert_eq!(
+        parse_arguments(vec![OsString::from("-directory")]).err(),
+        Some(String::from("Unrecognized argument '-directory'"))
+    );
+    assert_eq!(
+        parse_arguments(vec![
+            OsString::from("--"),
+            OsString::from("--hidden"),
+            OsString::from("--version"),
+        ])
+        .err(),
+        Some(String::from(
+            "Unexpected argument '--version'; only one path can be given"
+        ))
+    );
+}
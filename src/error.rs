@@ -0,0 +1,81 @@
+use std::{error, fmt, io, path::PathBuf};
+
+use crate::settings::SettingsError;
+
+#[derive(Debug)]
+pub enum Error {
+    Settings(SettingsError),
+    CurrentDirectory(io::Error),
+    StartingDirectory {
+        starting_directory_path: PathBuf,
+        io_error: io::Error,
+    },
+    Terminal(io::Error),
+    ChooseDirectory {
+        choose_directory_path: PathBuf,
+        io_error: io::Error,
+    },
+}
+
+impl From<SettingsError> for Error {
+    fn from(settings_error: SettingsError) -> Self {
+        Error::Settings(settings_error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Settings(settings_error) => write!(formatter, "{}", settings_error),
+            Error::CurrentDirectory(io_error) => write!(
+                formatter,
+                "could not read the current directory: {}",
+                get_io_error_message(io_error)
+            ),
+            Error::StartingDirectory {
+                starting_directory_path,
+                io_error,
+            } => write!(
+                formatter,
+                "could not open '{}': {}",
+                starting_directory_path.display(),
+                get_io_error_message(io_error)
+            ),
+            Error::Terminal(io_error) => write!(
+                formatter,
+                "could not interact with the terminal: {}",
+                get_io_error_message(io_error)
+            ),
+            Error::ChooseDirectory {
+                choose_directory_path,
+                io_error,
+            } => write!(
+                formatter,
+                "could not write to '{}': {}",
+                choose_directory_path.display(),
+                get_io_error_message(io_error)
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Settings(settings_error) => Some(settings_error),
+            Error::CurrentDirectory(io_error)
+            | Error::StartingDirectory { io_error, .. }
+            | Error::Terminal(io_error)
+            | Error::ChooseDirectory { io_error, .. } => Some(io_error),
+        }
+    }
+}
+
+/// Describes an `io::Error` briefly enough to be shown inside a directory container
+pub fn get_io_error_message(io_error: &io::Error) -> String {
+    match io_error.kind() {
+        io::ErrorKind::PermissionDenied => String::from("permission denied"),
+        io::ErrorKind::NotFound => String::from("not found"),
+        _ => io_error.to_string(),
+    }
+}
@@ -0,0 +1,159 @@
+use crate::utils::string::{
+    formatting::{get_display_width, take_display_width},
+    styling::{get_styled_text, Style},
+};
+
+/// Text that is output in a single style
+#[derive(Clone, PartialEq)]
+pub struct StyledText {
+    pub text: String,
+    pub style: Style,
+}
+
+/// Everything a `PathContainer` renders, line by line.  A frame doesn't output anything itself;
+/// a sink (such as stdout, or a string in tests) decides whether to include the styles.
+#[derive(Clone, PartialEq)]
+pub struct Frame {
+    line_vec: Vec<Vec<StyledText>>,
+}
+
+impl Default for Frame {
+    fn default() -> Self {
+        Frame::new()
+    }
+}
+
+impl Frame {
+    pub fn new() -> Self {
+        Frame {
+            line_vec: vec![Vec::new()],
+        }
+    }
+
+    pub fn push_text(&mut self, text: &str) {
+        self.push_styled_text(text, &Style::default());
+    }
+
+    pub fn push_styled_text(&mut self, text: &str, style: &Style) {
+        if text.is_empty() {
+            return;
+        }
+
+        let line = self.line_vec.last_mut().expect("A frame always has a line");
+
+        // Adjacent text in the same style is merged, so it is styled once
+        match line.last_mut() {
+            Some(styled_text) if &styled_text.style == style => styled_text.text.push_str(text),
+            _ => line.push(StyledText {
+                text: String::from(text),
+                style: style.clone(),
+            }),
+        }
+    }
+
+    pub fn push_line_break(&mut self) {
+        self.line_vec.push(Vec::new());
+    }
+
+    /// Cuts every line that is wider than `width` columns down to it, ending the line with
+    /// `clipped_text_indicator`
+    pub fn clip_lines(&mut self, width: usize, clipped_text_indicator: &str) {
+        let width_before_indicator =
+            width.saturating_sub(get_display_width(clipped_text_indicator));
+
+        for line in self.line_vec.iter_mut() {
+            let line_width: usize = line
+                .iter()
+                .map(|styled_text| get_display_width(&styled_text.text))
+                .sum();
+
+            if line_width <= width {
+                continue;
+            }
+
+            let mut remaining_width = width_before_indicator;
+            let mut clipped_line: Vec<StyledText> = Vec::new();
+
+            for styled_text in line.drain(..) {
+                let clipped_text = take_display_width(&styled_text.text, remaining_width);
+
+                if !clipped_text.is_empty() {
+                    remaining_width -= get_display_width(clipped_text);
+                    clipped_line.push(StyledText {
+                        text: String::from(clipped_text),
+                        style: styled_text.style.clone(),
+                    });
+                }
+
+                if clipped_text.len() < styled_text.text.len() {
+                    break;
+                }
+            }
+
+            *line = clipped_line;
+            line.push(StyledText {
+                text: String::from(clipped_text_indicator),
+                style: Style::default(),
+            });
+        }
+    }
+
+    /// Returns each line, with the escape sequences for its styles if `should_include_styles`
+    pub fn get_lines(&self, should_include_styles: bool) -> Vec<String> {
+        self.line_vec
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|styled_text| {
+                        if should_include_styles {
+                            get_styled_text(&styled_text.text, &styled_text.style)
+                        } else {
+                            styled_text.text.clone()
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn get_string(&self, should_include_styles: bool) -> String {
+        self.get_lines(should_include_styles).join("\n")
+    }
+}
+
+#[test]
+fn frame_get_lines() {
+    let bold_style = Style {
+        is_bold: true,
+        ..Style::default()
+    };
+    let mut frame = Frame::new();
+
+    frame.push_text("| ");
+    frame.push_styled_text("(D) ", &bold_style);
+    frame.push_styled_text("src", &bold_style);
+    frame.push_text(" |");
+    frame.push_line_break();
+
+    assert_eq!(frame.get_lines(false), vec!["| (D) src |", ""]);
+    assert_eq!(frame.get_string(true), "| \x1b[1m(D) src\x1b[0m |\n");
+}
+
+#[test]
+fn frame_clip_lines() {
+    let bold_style = Style {
+        is_bold: true,
+        ..Style::default()
+    };
+    let mut frame = Frame::new();
+
+    frame.push_text("| ");
+    frame.push_styled_text("a_long_file_name", &bold_style);
+    frame.push_text(" |");
+    frame.push_line_break();
+    frame.push_text("| short |");
+    frame.clip_lines(12, "...");
+
+    assert_eq!(frame.get_lines(false), vec!["| a_long_...", "| short |"]);
+    assert_eq!(frame.get_lines(true)[0], "| \x1b[1ma_long_\x1b[0m...");
+}
@@ -0,0 +1,290 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use git2::{Repository, Status, StatusOptions};
+
+mod ignore_rules;
+use ignore_rules::IgnoreRules;
+
+/// Ordered from the least to the most important, which is the status a directory shows when its
+/// contents have several
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum GitStatus {
+    Ignored,
+    Untracked,
+    Staged,
+    Modified,
+    Conflicted,
+}
+
+impl GitStatus {
+    fn from_status(status: Status) -> Option<Self> {
+        if status.is_conflicted() {
+            Some(GitStatus::Conflicted)
+        } else if status.intersects(
+            Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_RENAMED | Status::WT_TYPECHANGE,
+        ) {
+            Some(GitStatus::Modified)
+        } else if status.intersects(
+            Status::INDEX_NEW
+                | Status::INDEX_MODIFIED
+                | Status::INDEX_DELETED
+                | Status::INDEX_RENAMED
+                | Status::INDEX_TYPECHANGE,
+        ) {
+            Some(GitStatus::Staged)
+        } else if status.is_wt_new() {
+            Some(GitStatus::Untracked)
+        } else if status.is_ignored() {
+            Some(GitStatus::Ignored)
+        } else {
+            None
+        }
+    }
+}
+
+/// The status of every changed path in a git work tree, read once from the local `.git`
+/// directory, and its ignore rules
+pub struct GitRepository {
+    work_tree_path: PathBuf,
+    status_map: HashMap<PathBuf, GitStatus>,
+    directory_status_map: HashMap<PathBuf, GitStatus>, // The most important status inside
+    ignore_rules: IgnoreRules,
+}
+
+impl GitRepository {
+    /// Returns `None` when the statuses can't be read, so the work tree is shown without them
+    fn new(
+        repository: &Repository,
+        work_tree_path: PathBuf,
+        should_read_git_status: bool,
+    ) -> Option<Self> {
+        let (status_map, directory_status_map) = if should_read_git_status {
+            GitRepository::read_statuses(repository, &work_tree_path)?
+        } else {
+            (HashMap::new(), HashMap::new())
+        };
+
+        Some(GitRepository {
+            ignore_rules: IgnoreRules::new(work_tree_path.clone(), repository.commondir()),
+            work_tree_path,
+            status_map,
+            directory_status_map,
+        })
+    }
+
+    /// Returns the status of each changed path, and the most important status inside each
+    /// directory that has changes
+    fn read_statuses(
+        repository: &Repository,
+        work_tree_path: &Path,
+    ) -> Option<(HashMap<PathBuf, GitStatus>, HashMap<PathBuf, GitStatus>)> {
+        // Untracked and ignored directories are listed once, instead of with all their contents
+        let mut status_options = StatusOptions::new();
+        status_options
+            .include_untracked(true)
+            .recurse_untracked_dirs(false)
+            .include_ignored(true)
+            .recurse_ignored_dirs(false);
+
+        let statuses = repository.statuses(Some(&mut status_options)).ok()?;
+        let mut status_map: HashMap<PathBuf, GitStatus> = HashMap::new();
+        let mut directory_status_map: HashMap<PathBuf, GitStatus> = HashMap::new();
+
+        for status_entry in statuses.iter() {
+            let git_status = match GitStatus::from_status(status_entry.status()) {
+                Some(git_status) => git_status,
+                None => continue,
+            };
+            let path = match status_entry.path() {
+                Some(relative_path) => work_tree_path.join(relative_path),
+                None => continue,
+            };
+
+            // A single ignored file doesn't make the directories it is in ignored
+            if git_status != GitStatus::Ignored {
+                for ancestor_path in path
+                    .ancestors()
+                    .skip(1)
+                    .take_while(|ancestor_path| ancestor_path.starts_with(work_tree_path))
+                {
+                    let directory_status = directory_status_map
+                        .entry(ancestor_path.to_path_buf())
+                        .or_insert(git_status);
+                    *directory_status = std::cmp::max(*directory_status, git_status);
+                }
+            }
+
+            status_map.insert(path, git_status);
+        }
+
+        Some((status_map, directory_status_map))
+    }
+
+    pub fn get_work_tree_path(&self) -> &Path {
+        &self.work_tree_path
+    }
+
+    /// Whether `.ignore`, `.gitignore`, `.git/info/exclude` or the global excludes file ignore
+    /// `path`
+    pub fn is_ignored(&self, path: &Path, is_directory: bool) -> bool {
+        self.ignore_rules.is_ignored(path, is_directory)
+    }
+
+    /// Returns `None` for unchanged paths and paths outside the work tree.  Paths inside an
+    /// untracked or ignored directory share its status.
+    pub fn get_git_status_option(&self, path: &Path) -> Option<GitStatus> {
+        let git_status_option = std::cmp::max(
+            self.status_map.get(path).copied(),
+            self.directory_status_map.get(path).copied(),
+        );
+
+        if git_status_option.is_some() {
+            return git_status_option;
+        }
+
+        path.ancestors()
+            .skip(1)
+            .take_while(|ancestor_path| ancestor_path.starts_with(&self.work_tree_path))
+            .find_map(|ancestor_path| match self.status_map.get(ancestor_path) {
+                Some(GitStatus::Untracked) => Some(GitStatus::Untracked),
+                Some(GitStatus::Ignored) => Some(GitStatus::Ignored),
+                _ => None,
+            })
+    }
+}
+
+/// Reads each git work tree once, however many of its directories are shown
+pub struct GitRepositoryCache {
+    git_repository_vec: Vec<Rc<GitRepository>>,
+    should_read_git_status: bool, // Only the ignore rules are needed otherwise
+}
+
+impl GitRepositoryCache {
+    pub fn new(should_read_git_status: bool) -> Self {
+        GitRepositoryCache {
+            git_repository_vec: Vec::new(),
+            should_read_git_status,
+        }
+    }
+
+    /// Finds the work tree that `directory_path` is inside of, if any.  Bare repositories and the
+    /// directories above a work tree have none.
+    pub fn get_git_repository_option(
+        &mut self,
+        directory_path: &Path,
+    ) -> Option<Rc<GitRepository>> {
+        if let Some(git_repository) = self.get_cached_git_repository_option(directory_path) {
+            return Some(git_repository);
+        }
+
+        let repository = Repository::discover(directory_path).ok()?;
+        let work_tree_path = repository.workdir()?.to_path_buf();
+
+        if let Some(git_repository) = self
+            .git_repository_vec
+            .iter()
+            .find(|git_repository| git_repository.work_tree_path == work_tree_path)
+        {
+            return Some(Rc::clone(git_repository));
+        }
+
+        let git_repository = Rc::new(GitRepository::new(
+            &repository,
+            work_tree_path,
+            self.should_read_git_status,
+        )?);
+        self.git_repository_vec.push(Rc::clone(&git_repository));
+
+        Some(git_repository)
+    }
+
+    /// Avoids discovering the repository again for every directory inside a known work tree,
+    /// unless a nested repository (or the `.git` directory) is in between
+    fn get_cached_git_repository_option(&self, directory_path: &Path) -> Option<Rc<GitRepository>> {
+        let git_repository = self
+            .git_repository_vec
+            .iter()
+            .filter(|git_repository| directory_path.starts_with(&git_repository.work_tree_path))
+            .max_by_key(|git_repository| git_repository.work_tree_path.components().count())?;
+
+        let is_in_nested_repository = directory_path
+            .ancestors()
+            .take_while(|path| *path != git_repository.work_tree_path)
+            .any(|path| path.file_name() == Some(".git".as_ref()) || path.join(".git").exists());
+
+        if is_in_nested_repository {
+            return None;
+        }
+
+        Some(Rc::clone(git_repository))
+    }
+}
+
+#[test]
+fn git_status_from_status_precedence() {
+    assert_eq!(
+        GitStatus::from_status(Status::INDEX_MODIFIED | Status::WT_MODIFIED),
+        Some(GitStatus::Modified)
+    );
+    assert_eq!(
+        GitStatus::from_status(Status::INDEX_NEW),
+        Some(GitStatus::Staged)
+    );
+    assert_eq!(
+        GitStatus::from_status(Status::CONFLICTED | Status::WT_MODIFIED),
+        Some(GitStatus::Conflicted)
+    );
+    assert_eq!(GitStatus::from_status(Status::CURRENT), None);
+    assert!(GitStatus::Conflicted > GitStatus::Untracked);
+}
+
+#[test]
+fn git_repository_cache_nested_repository() {
+    // git2 reports canonical work tree paths
+    let temporary_directory_path =
+        std::fs::canonicalize(std::env::temp_dir())
+            .unwrap()
+            .join(format!(
+                "termifind-git-repository-cache-{}",
+                std::process::id()
+            ));
+    let work_tree_path = temporary_directory_path.join("repository");
+    let nested_work_tree_path = work_tree_path.join("nested_repository");
+    std::fs::create_dir_all(work_tree_path.join("directory")).unwrap();
+    std::fs::create_dir_all(nested_work_tree_path.join("directory")).unwrap();
+    Repository::init(&work_tree_path).unwrap();
+    Repository::init(&nested_work_tree_path).unwrap();
+
+    let mut git_repository_cache = GitRepositoryCache::new(false);
+    let get_work_tree_path = |git_repository_cache: &mut GitRepositoryCache, path: &Path| {
+        git_repository_cache
+            .get_git_repository_option(path)
+            .map(|git_repository| git_repository.work_tree_path.clone())
+    };
+    let work_tree_path_options = [
+        get_work_tree_path(&mut git_repository_cache, &work_tree_path),
+        get_work_tree_path(&mut git_repository_cache, &work_tree_path.join("directory")),
+        get_work_tree_path(
+            &mut git_repository_cache,
+            &nested_work_tree_path.join("directory"),
+        ),
+        get_work_tree_path(&mut git_repository_cache, &nested_work_tree_path),
+    ];
+    let number_of_git_repositories = git_repository_cache.git_repository_vec.len();
+    std::fs::remove_dir_all(&temporary_directory_path).unwrap();
+
+    assert_eq!(
+        work_tree_path_options,
+        [
+            Some(work_tree_path.clone()),
+            Some(work_tree_path),
+            Some(nested_work_tree_path.clone()),
+            Some(nested_work_tree_path),
+        ]
+    );
+    assert_eq!(number_of_git_repositories, 2);
+}
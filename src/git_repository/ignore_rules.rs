@@ -0,0 +1,152 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use ignore::{
+    gitignore::{gitconfig_excludes_path, Gitignore, GitignoreBuilder},
+    Match,
+};
+
+const IGNORE_FILE_NAME: &str = ".ignore";
+const GITIGNORE_FILE_NAME: &str = ".gitignore";
+
+/// The `.ignore` and `.gitignore` rules of a single directory
+struct DirectoryIgnoreRules {
+    ignore: Gitignore,
+    gitignore: Gitignore,
+}
+
+/// The ignore rules of a git work tree.  Each directory's `.ignore` and `.gitignore` files are
+/// read the first time a path inside of it is checked, and are kept for as long as the work tree
+/// is.
+pub struct IgnoreRules {
+    work_tree_path: PathBuf,
+    exclude: Gitignore,
+    global_excludes: Gitignore,
+    directory_ignore_rules_map: RefCell<HashMap<PathBuf, Rc<DirectoryIgnoreRules>>>,
+}
+
+/// Files that are missing or can't be read have no rules
+fn build_gitignore(root_path: &Path, gitignore_path_option: Option<&Path>) -> Gitignore {
+    let mut gitignore_builder = GitignoreBuilder::new(root_path);
+
+    if let Some(gitignore_path) = gitignore_path_option {
+        if gitignore_path.is_file() {
+            gitignore_builder.add(gitignore_path);
+        }
+    }
+
+    gitignore_builder
+        .build()
+        .unwrap_or_else(|_| Gitignore::empty())
+}
+
+impl IgnoreRules {
+    /// `git_directory_path` is the directory that holds `info/exclude`, which linked work trees
+    /// share with the main one
+    pub fn new(work_tree_path: PathBuf, git_directory_path: &Path) -> Self {
+        IgnoreRules {
+            exclude: build_gitignore(
+                &work_tree_path,
+                Some(&git_directory_path.join("info").join("exclude")),
+            ),
+            global_excludes: build_gitignore(&work_tree_path, gitconfig_excludes_path().as_deref()),
+            work_tree_path,
+            directory_ignore_rules_map: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn get_directory_ignore_rules(&self, directory_path: &Path) -> Rc<DirectoryIgnoreRules> {
+        Rc::clone(
+            self.directory_ignore_rules_map
+                .borrow_mut()
+                .entry(directory_path.to_path_buf())
+                .or_insert_with(|| {
+                    Rc::new(DirectoryIgnoreRules {
+                        ignore: build_gitignore(
+                            directory_path,
+                            Some(&directory_path.join(IGNORE_FILE_NAME)),
+                        ),
+                        gitignore: build_gitignore(
+                            directory_path,
+                            Some(&directory_path.join(GITIGNORE_FILE_NAME)),
+                        ),
+                    })
+                }),
+        )
+    }
+
+    /// Checks the rules from the closest directory outwards, with `.ignore` files taking
+    /// precedence over `.gitignore` files, then `.git/info/exclude`, then the global excludes
+    /// file.  Like git, a path inside an ignored directory is ignored too, even if a deeper rule
+    /// would include it again.
+    pub fn is_ignored(&self, path: &Path, is_directory: bool) -> bool {
+        if !path
+            .parent()
+            .is_some_and(|parent_path| parent_path.starts_with(&self.work_tree_path))
+        {
+            return false;
+        }
+
+        let mut directory_path_vec: Vec<&Path> = path
+            .ancestors()
+            .skip(1)
+            .take_while(|directory_path| *directory_path != self.work_tree_path)
+            .collect();
+        directory_path_vec.reverse();
+
+        directory_path_vec
+            .into_iter()
+            .any(|directory_path| self.get_match_option(directory_path, true) == Some(true))
+            || self.get_match_option(path, is_directory).unwrap_or(false)
+    }
+
+    /// Whether the closest rule that matches `path` itself ignores it
+    fn get_match_option(&self, path: &Path, is_directory: bool) -> Option<bool> {
+        let get_match = |gitignore: &Gitignore| match gitignore.matched(path, is_directory) {
+            Match::Ignore(_) => Some(true),
+            Match::Whitelist(_) => Some(false),
+            Match::None => None,
+        };
+
+        for directory_path in path
+            .ancestors()
+            .skip(1)
+            .take_while(|directory_path| directory_path.starts_with(&self.work_tree_path))
+        {
+            let directory_ignore_rules = self.get_directory_ignore_rules(directory_path);
+
+            if let Some(is_ignored) = get_match(&directory_ignore_rules.ignore)
+                .or_else(|| get_match(&directory_ignore_rules.gitignore))
+            {
+                return Some(is_ignored);
+            }
+        }
+
+        get_match(&self.exclude).or_else(|| get_match(&self.global_excludes))
+    }
+}
+
+#[test]
+fn is_ignored_inside_an_ignored_directory() {
+    let work_tree_path =
+        std::env::temp_dir().join(format!("termifind-ignore-rules-{}", std::process::id()));
+    let directory_path = work_tree_path.join("directory");
+    std::fs::create_dir_all(directory_path.join("nested_directory")).unwrap();
+    std::fs::write(work_tree_path.join(GITIGNORE_FILE_NAME), "directory/\n").unwrap();
+    std::fs::write(directory_path.join(GITIGNORE_FILE_NAME), "!file\n").unwrap();
+
+    let ignore_rules = IgnoreRules::new(work_tree_path.clone(), &work_tree_path.join(".git"));
+    let is_ignored_vec = [
+        ignore_rules.is_ignored(&directory_path, true),
+        ignore_rules.is_ignored(&directory_path.join("file"), false),
+        ignore_rules.is_ignored(&directory_path.join("nested_directory/file"), false),
+        ignore_rules.is_ignored(&work_tree_path.join("file"), false),
+    ];
+    std::fs::remove_dir_all(&work_tree_path).unwrap();
+
+    assert_eq!(is_ignored_vec, [true, true, true, false]);
+}
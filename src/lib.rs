@@ -0,0 +1,20 @@
+//! The directory browsing and rendering behind the `termifind` binary.  A `PathContainer` holds a
+//! `DirectoryContainer` for each directory from the root down to a path, and renders them into a
+//! `Frame`, or directly into a string with `PathContainer::render_to_string`.
+
+pub mod error;
+pub mod frame;
+pub mod git_repository;
+pub mod path_container;
+pub mod settings;
+pub mod utils;
+
+pub use frame::Frame;
+pub use path_container::{
+    directory_container::{DirectoryContainer, DirectoryItem, ItemState, ItemType},
+    PathContainer,
+};
+pub use settings::{
+    DirectoryContainerSettings, DirectoryItemSettings, PathContainerSettings, Settings,
+    ThemeSettings,
+};
@@ -1,34 +1,310 @@
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    fs::{canonicalize, write},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
 
-mod path_container;
-use path_container::PathContainer;
+use console::{Key, Term};
 
-mod settings;
-mod utils;
+use termifind::{
+    error::Error,
+    settings::{self, HeightOptions},
+    Frame, PathContainer, Settings,
+};
 
-#[allow(dead_code)]
-enum ArrowKeys {
+mod arguments;
+use arguments::{Arguments, ColorOptions};
+
+// Used when stderr is not a terminal, such as when run from cron; only the width is used then,
+// as the height isn't limited
+const DEFAULT_TERMINAL_DIMENSIONS: (usize, usize) = (80, 24);
+
+enum Command {
     Up,
     Down,
     Left,
     Right,
+    CycleSortKey,
+    ReverseSortOrder,
+    ToggleHiddenFiles,
+    CycleTruncationOptions,
+}
+
+fn event_loop(
+    starting_directory_path: PathBuf,
+    mut settings: Settings,
+    terminal_dimensions: (usize, usize),
+) -> Result<Option<PathBuf>, Error> {
+    // The interface is drawn on stderr, so stdout is left for the chosen path and
+    // `cd "$(termifind)"` works
+    let terminal = Term::stderr();
+    let is_terminal_attended = terminal.features().is_attended();
+    let should_include_styles = if is_terminal_attended {
+        console::colors_enabled_stderr()
+    } else {
+        console::colors_enabled()
+    };
+    settings.theme.should_use_text_markers = !should_include_styles;
+
+    let height_options = &mut settings
+        .path_container_settings
+        .directory_container_settings
+        .height_options;
+
+    // Without a terminal there is no height to fit in, and no way to scroll
+    if !is_terminal_attended && matches!(height_options, HeightOptions::TerminalHeight) {
+        *height_options = HeightOptions::NoLimit;
+    }
+
+    let mut path_container: PathContainer = PathContainer::new(
+        starting_directory_path,
+        settings.path_container_settings,
+        settings.theme,
+        terminal_dimensions,
+    );
+
+    // There is nothing to interact with, so print the path once
+    if !is_terminal_attended {
+        print!(
+            "{}",
+            path_container.render().get_string(should_include_styles)
+        );
+        return Ok(None);
+    }
+
+    terminal.hide_cursor().map_err(Error::Terminal)?;
+
+    let chosen_path_option_result =
+        read_keys_until_exit(&terminal, terminal_dimensions.1, &mut path_container);
+
+    // Restore the terminal even if reading keys failed
+    terminal.clear_screen().map_err(Error::Terminal)?;
+    terminal.show_cursor().map_err(Error::Terminal)?;
+
+    chosen_path_option_result
 }
 
-fn event_loop() {
-    let settings = settings::get_settings();
-    let current_directory: PathBuf = env::current_dir().expect("Oops");
-    let path_container: PathContainer =
-        PathContainer::new(current_directory, settings.path_container_settings);
-    path_container.print_path();
+fn read_keys_until_exit(
+    terminal: &Term,
+    terminal_height: usize,
+    path_container: &mut PathContainer,
+) -> Result<Option<PathBuf>, Error> {
+    let mut previous_frame_option: Option<Frame> = None;
+
+    loop {
+        let mut frame = path_container.render();
+        path_container.render_status_line(&mut frame);
+        print_frame(terminal, terminal_height, &frame, &previous_frame_option)
+            .map_err(Error::Terminal)?;
+        previous_frame_option = Some(frame);
+
+        // `read_key` puts the terminal in raw mode for the duration of the read
+        let command = match terminal.read_key().map_err(Error::Terminal)? {
+            Key::ArrowUp | Key::Char('k') => Command::Up,
+            Key::ArrowDown | Key::Char('j') => Command::Down,
+            Key::ArrowLeft | Key::Char('h') => Command::Left,
+            Key::ArrowRight | Key::Char('l') => Command::Right,
+            Key::Char('s') => Command::CycleSortKey,
+            Key::Char('r') => Command::ReverseSortOrder,
+            Key::Char('.') => Command::ToggleHiddenFiles,
+            Key::Char('t') => Command::CycleTruncationOptions,
+            Key::Enter => return Ok(Some(path_container.get_chosen_path())),
+            Key::Escape | Key::Char('q') => return Ok(None),
+            _ => continue,
+        };
+
+        match command {
+            Command::Up => path_container.select_previous_directory_item(),
+            Command::Down => path_container.select_next_directory_item(),
+            Command::Left => path_container.leave_current_directory(),
+            Command::Right => path_container.enter_selected_directory(),
+            Command::CycleSortKey => path_container.cycle_sort_key(),
+            Command::ReverseSortOrder => path_container.reverse_sort_order(),
+            Command::ToggleHiddenFiles => path_container.toggle_hidden_files(),
+            Command::CycleTruncationOptions => path_container.cycle_truncation_options(),
+        }
+    }
+}
+
+/// Only rewrites the lines that changed since the previous frame.  A frame taller than the
+/// terminal scrolls it, so line numbers no longer match screen rows and it is printed in full.
+fn print_frame(
+    terminal: &Term,
+    terminal_height: usize,
+    frame: &Frame,
+    previous_frame_option: &Option<Frame>,
+) -> io::Result<()> {
+    let should_include_styles = console::colors_enabled_stderr();
+    let line_vec = frame.get_lines(should_include_styles);
+
+    match previous_frame_option {
+        Some(previous_frame) if line_vec.len() <= terminal_height => {
+            let previous_line_vec = previous_frame.get_lines(should_include_styles);
+
+            if previous_line_vec.len() > terminal_height {
+                return print_full_frame(terminal, &line_vec);
+            }
+
+            for index in 0..std::cmp::max(line_vec.len(), previous_line_vec.len()) {
+                let line_option = line_vec.get(index);
+
+                if line_option != previous_line_vec.get(index) {
+                    terminal.move_cursor_to(0, index)?;
+                    terminal.clear_line()?;
+                    terminal.write_str(line_option.map_or("", String::as_str))?;
+                }
+            }
+
+            Ok(())
+        }
+        _ => print_full_frame(terminal, &line_vec),
+    }
+}
+
+fn print_full_frame(terminal: &Term, line_vec: &[String]) -> io::Result<()> {
+    terminal.clear_screen()?;
+    terminal.write_str(&line_vec.join("\n"))
+}
+
+fn get_terminal_dimensions(width_option: Option<usize>) -> (usize, usize) {
+    let (terminal_width, terminal_height) =
+        term_size::dimensions_stderr().unwrap_or(DEFAULT_TERMINAL_DIMENSIONS);
+
+    (width_option.unwrap_or(terminal_width), terminal_height)
+}
+
+fn set_colors_enabled(color_options: &ColorOptions) {
+    // `console` already checks each stream for a terminal and `CLICOLOR`; https://no-color.org
+    let is_no_color_set = env::var_os("NO_COLOR").is_some_and(|no_color| !no_color.is_empty());
+    let get_colors_enabled = |are_colors_supported: bool| match color_options {
+        ColorOptions::Auto => are_colors_supported && !is_no_color_set,
+        ColorOptions::Always => true,
+        ColorOptions::Never => false,
+    };
+
+    console::set_colors_enabled(get_colors_enabled(console::colors_enabled()));
+    console::set_colors_enabled_stderr(get_colors_enabled(console::colors_enabled_stderr()));
+}
+
+/// Resolves `path` to an absolute path without `..` components, as `PathContainer` walks up
+/// through its parents
+fn get_starting_directory_path(path: &Path) -> Result<PathBuf, Error> {
+    let get_error = |io_error| Error::StartingDirectory {
+        starting_directory_path: path.to_path_buf(),
+        io_error,
+    };
+    let starting_directory_path = canonicalize(path).map_err(get_error)?;
+
+    if !starting_directory_path.is_dir() {
+        return Err(get_error(io::Error::new(
+            io::ErrorKind::NotADirectory,
+            "not a directory",
+        )));
+    }
+
+    Ok(starting_directory_path)
+}
+
+/// Paths that aren't valid UTF-8 are written as they are, so the shell wrappers can still `cd`
+/// into them
+#[cfg(unix)]
+fn get_path_bytes(path: &Path) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+
+    path.as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(not(unix))]
+fn get_path_bytes(path: &Path) -> Vec<u8> {
+    path.to_string_lossy().into_owned().into_bytes()
+}
+
+fn run(arguments: Arguments) -> Result<(), Error> {
+    if arguments.should_print_help {
+        print!("{}", arguments::HELP_TEXT);
+        return Ok(());
+    }
+
+    if arguments.should_print_version {
+        println!("termifind {}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
+
+    let settings_paths = settings::get_settings_paths(&arguments.settings_path_option);
+
+    if arguments.should_print_settings_path {
+        if settings_paths.is_empty() {
+            println!("No settings file found; using the default settings");
+        }
+
+        for settings_path in &settings_paths {
+            println!("{}", settings_path.display());
+        }
+
+        return Ok(());
+    }
+
+    let mut settings = settings::get_settings(&settings_paths)?;
+
+    if arguments.should_check_settings {
+        if settings_paths.is_empty() {
+            println!("No settings file found; using the default settings");
+        }
+
+        for settings_path in &settings_paths {
+            println!("'{}' is valid", settings_path.display());
+        }
+
+        return Ok(());
+    }
+
+    arguments.apply_to_settings(&mut settings);
+    set_colors_enabled(&arguments.color_options);
+
+    let starting_directory_path = match &arguments.path_option {
+        Some(path) => get_starting_directory_path(path)?,
+        None => env::current_dir().map_err(Error::CurrentDirectory)?,
+    };
+
+    if let Some(chosen_path) = event_loop(
+        starting_directory_path,
+        settings,
+        get_terminal_dimensions(arguments.width_option),
+    )? {
+        let chosen_path_bytes = get_path_bytes(&chosen_path);
 
-    // loop {
+        match arguments.choose_directory_path_option {
+            Some(choose_directory_path) => write(&choose_directory_path, chosen_path_bytes)
+                .map_err(|io_error| Error::ChooseDirectory {
+                    choose_directory_path,
+                    io_error,
+                })?,
+            None => {
+                let mut stdout = io::stdout();
+                stdout
+                    .write_all(&chosen_path_bytes)
+                    .and_then(|_| stdout.write_all(b"\n"))
+                    .map_err(Error::Terminal)?
+            }
+        }
+    }
 
-    // }
+    Ok(())
 }
 
 fn main() {
-    event_loop();
+    // Invalid arguments exit with 2, like other command line tools, and everything else with 1
+    let arguments = match Arguments::new() {
+        Ok(arguments) => arguments,
+        Err(message) => {
+            eprintln!("termifind: {}", message);
+            std::process::exit(2);
+        }
+    };
 
-    // Return string to console here somehow
-    // String::from("HELLO");
+    if let Err(error) = run(arguments) {
+        eprintln!("termifind: {}", error);
+        std::process::exit(1);
+    }
 }
@@ -1,26 +1,45 @@
 use std::{
     collections::VecDeque,
     path::{Path, PathBuf},
+    rc::Rc,
 };
 
-mod directory_container;
+pub mod directory_container;
 use directory_container::{DirectoryContainer, ItemState};
 
 use crate::{
-    settings::PathContainerSettings, utils::string::formatting::make_repeated_char_string,
+    frame::Frame,
+    git_repository::{GitRepository, GitRepositoryCache},
+    settings::{
+        HeightOptions, IgnoredFileOptions, PathContainerSettings, SortKey, ThemeSettings,
+        TruncationOptions,
+    },
+    utils::string::formatting::{get_display_width, make_repeated_char_string},
 };
 
-#[allow(dead_code)]
 pub struct PathContainer {
     current_path: PathBuf,
     directory_container_vec_deque: VecDeque<DirectoryContainer>,
     terminal_dimensions: (usize, usize),
+    git_repository_cache: GitRepositoryCache,
+    configured_sort_key_vec: Vec<SortKey>, // Cycling the sort key returns to these
     path_container_settings: PathContainerSettings,
+    theme: ThemeSettings,
 }
 
 impl PathContainer {
-    pub fn new(path: PathBuf, path_container_settings: PathContainerSettings) -> Self {
+    pub fn new(
+        path: PathBuf,
+        path_container_settings: PathContainerSettings,
+        theme: ThemeSettings,
+        terminal_dimensions: (usize, usize),
+    ) -> Self {
         let mut directory_container_vec_deque: VecDeque<DirectoryContainer> = VecDeque::new();
+        let mut git_repository_cache = GitRepositoryCache::new(
+            path_container_settings
+                .directory_container_settings
+                .should_display_git_status,
+        );
         let mut parent_path: &Path = &path;
 
         loop {
@@ -28,6 +47,12 @@ impl PathContainer {
                 parent_path.to_path_buf(),
                 &PathContainer::get_selected_directory_option(&directory_container_vec_deque),
                 path_container_settings.directory_container_settings.clone(),
+                theme.clone(),
+                PathContainer::get_git_repository_option(
+                    &mut git_repository_cache,
+                    parent_path,
+                    &path_container_settings,
+                ),
             ));
 
             match parent_path.parent() {
@@ -40,79 +65,615 @@ impl PathContainer {
             &mut directory_container_vec_deque,
         );
 
-        PathContainer {
+        let mut path_container = PathContainer {
             current_path: path,
             directory_container_vec_deque,
-            terminal_dimensions: term_size::dimensions().expect("Oops"),
+            terminal_dimensions,
+            git_repository_cache,
+            configured_sort_key_vec: path_container_settings
+                .directory_container_settings
+                .sort_settings
+                .sort_key_vec
+                .clone(),
             path_container_settings,
+            theme,
+        };
+
+        path_container.set_terminal_dimensions(terminal_dimensions);
+
+        path_container
+    }
+
+    /// Lays the path out again for a terminal of a different size
+    pub fn set_terminal_dimensions(&mut self, terminal_dimensions: (usize, usize)) {
+        self.terminal_dimensions = terminal_dimensions;
+        self.apply_truncation_options();
+        self.apply_terminal_height_limit();
+    }
+
+    fn get_git_repository_option(
+        git_repository_cache: &mut GitRepositoryCache,
+        directory_path: &Path,
+        path_container_settings: &PathContainerSettings,
+    ) -> Option<Rc<GitRepository>> {
+        let directory_container_settings = &path_container_settings.directory_container_settings;
+
+        if !directory_container_settings.should_display_git_status
+            && directory_container_settings.ignored_file_options == IgnoredFileOptions::Display
+        {
+            return None;
         }
+
+        git_repository_cache.get_git_repository_option(directory_path)
     }
 
     fn get_selected_directory_option(
         directory_container_vec_deque: &VecDeque<DirectoryContainer>,
     ) -> Option<PathBuf> {
-        if directory_container_vec_deque.is_empty() {
-            return None;
-        }
-        match directory_container_vec_deque.front() {
-            Some(first_directory_container) => {
-                Some(first_directory_container.path_to_directory.clone())
-            }
-            None => None,
-        }
+        directory_container_vec_deque
+            .front()
+            .map(|first_directory_container| first_directory_container.path_to_directory.clone())
     }
 
     fn select_first_directory_item_in_current_directory_container(
         directory_container_vec_deque: &mut VecDeque<DirectoryContainer>,
     ) {
         if let Some(directory_container) = directory_container_vec_deque.back_mut() {
-            if let Some(directory_item) = directory_container.directory_item_vec.first_mut() {
-                directory_item.item_state = ItemState::Selected
+            directory_container.select_first_directory_item();
+        }
+    }
+
+    pub fn select_previous_directory_item(&mut self) {
+        if let Some(directory_container) = self.directory_container_vec_deque.back_mut() {
+            directory_container.select_previous_directory_item();
+        }
+    }
+
+    pub fn select_next_directory_item(&mut self) {
+        if let Some(directory_container) = self.directory_container_vec_deque.back_mut() {
+            directory_container.select_next_directory_item();
+        }
+    }
+
+    pub fn enter_selected_directory(&mut self) {
+        let selected_directory_path: PathBuf = match self
+            .directory_container_vec_deque
+            .back_mut()
+            .and_then(|directory_container| directory_container.get_selected_directory_item_mut())
+        {
+            Some(directory_item) if directory_item.is_directory() => {
+                directory_item.item_state = ItemState::DirectoryInPath;
+                directory_item.directory_entry.path()
             }
+            _ => return,
+        };
+
+        let git_repository_option = PathContainer::get_git_repository_option(
+            &mut self.git_repository_cache,
+            &selected_directory_path,
+            &self.path_container_settings,
+        );
+
+        self.directory_container_vec_deque
+            .push_back(DirectoryContainer::new(
+                selected_directory_path.clone(),
+                &None,
+                self.path_container_settings
+                    .directory_container_settings
+                    .clone(),
+                self.theme.clone(),
+                git_repository_option,
+            ));
+
+        PathContainer::select_first_directory_item_in_current_directory_container(
+            &mut self.directory_container_vec_deque,
+        );
+
+        self.current_path = selected_directory_path;
+
+        self.apply_truncation_options();
+        self.apply_terminal_height_limit();
+    }
+
+    pub fn leave_current_directory(&mut self) {
+        if self.directory_container_vec_deque.len() <= 1 {
+            return;
+        }
+
+        self.directory_container_vec_deque.pop_back();
+
+        if let Some(directory_container) = self.directory_container_vec_deque.back_mut() {
+            directory_container.select_directory_item_in_path();
+            self.current_path = directory_container.path_to_directory.clone();
         }
+
+        self.apply_truncation_options();
+        self.apply_terminal_height_limit();
     }
 
-    pub fn print_path(&self) {
-        let starting_index = match self
+    /// Sorts by the configured sort keys, then by each sort key on its own, in every directory
+    /// container.  A single configured sort key is only sorted by once per cycle.
+    pub fn cycle_sort_key(&mut self) {
+        let configured_sort_key_vec = &self.configured_sort_key_vec;
+        let sort_key_vec = &mut self
             .path_container_settings
-            .number_of_directory_containers_to_print_option
-        {
-            Some(number_of_directory_containers_to_print) => {
-                if number_of_directory_containers_to_print
-                    <= self.directory_container_vec_deque.len()
-                {
-                    self.directory_container_vec_deque.len()
-                        - number_of_directory_containers_to_print
-                } else {
-                    0
-                }
+            .directory_container_settings
+            .sort_settings
+            .sort_key_vec;
+        let get_next_sort_key_option = |sort_key: SortKey| match sort_key {
+            SortKey::ItemType => None,
+            _ => Some(sort_key.get_next()),
+        };
+
+        let mut next_sort_key_option = match sort_key_vec.as_slice() {
+            _ if sort_key_vec == configured_sort_key_vec => Some(SortKey::Name),
+            [sort_key] => get_next_sort_key_option(*sort_key),
+            _ => None,
+        };
+
+        if let Some(next_sort_key) = next_sort_key_option {
+            if [next_sort_key] == configured_sort_key_vec.as_slice() {
+                next_sort_key_option = get_next_sort_key_option(next_sort_key);
             }
-            None => 0,
+        }
+
+        *sort_key_vec = match next_sort_key_option {
+            Some(next_sort_key) => vec![next_sort_key],
+            None => configured_sort_key_vec.clone(),
         };
 
+        self.apply_directory_container_settings();
+    }
+
+    pub fn reverse_sort_order(&mut self) {
+        let sort_settings = &mut self
+            .path_container_settings
+            .directory_container_settings
+            .sort_settings;
+        sort_settings.should_sort_in_descending_order =
+            !sort_settings.should_sort_in_descending_order;
+
+        self.apply_directory_container_settings();
+    }
+
+    pub fn toggle_hidden_files(&mut self) {
+        let directory_container_settings =
+            &mut self.path_container_settings.directory_container_settings;
+        directory_container_settings.should_display_hidden_files =
+            !directory_container_settings.should_display_hidden_files;
+
+        self.apply_directory_container_settings();
+    }
+
+    pub fn cycle_truncation_options(&mut self) {
+        let directory_container_settings =
+            &mut self.path_container_settings.directory_container_settings;
+        directory_container_settings.truncation_options =
+            directory_container_settings.truncation_options.get_next();
+
+        self.apply_directory_container_settings();
+    }
+
+    /// Passes the path container's directory container settings, after they have been changed
+    /// while browsing, on to every directory container and lays the path out again
+    fn apply_directory_container_settings(&mut self) {
+        for directory_container in self.directory_container_vec_deque.iter_mut() {
+            directory_container.set_directory_container_settings(
+                self.path_container_settings
+                    .directory_container_settings
+                    .clone(),
+            );
+        }
+
+        self.apply_truncation_options();
+        self.apply_terminal_height_limit();
+    }
+
+    pub fn get_chosen_path(&self) -> PathBuf {
+        self.directory_container_vec_deque
+            .back()
+            .and_then(|directory_container| directory_container.get_selected_directory_item())
+            .map(|directory_item| directory_item.directory_entry.path())
+            .unwrap_or_else(|| self.current_path.clone())
+    }
+
+    /// Limits every directory container to as many directory items as fit in the terminal when
+    /// each row of directory containers gets an equal share of its height, leaving room for their
+    /// borders, the lines between the rows and one line for the cursor.  Limiting the heights can
+    /// widen the directory containers, and so wrap them onto more rows, so the truncation options
+    /// are applied again until the number of rows settles.
+    fn apply_terminal_height_limit(&mut self) {
+        if !matches!(
+            self.path_container_settings
+                .directory_container_settings
+                .height_options,
+            HeightOptions::TerminalHeight
+        ) {
+            return;
+        }
+
+        let mut number_of_rows = 1;
+
+        loop {
+            let number_of_lines_between_rows = (number_of_rows - 1)
+                * self
+                    .path_container_settings
+                    .spaces_between_directory_container_rows;
+            let height_of_row = self
+                .terminal_dimensions
+                .1
+                .saturating_sub(number_of_lines_between_rows + 1)
+                / number_of_rows;
+
+            for directory_container in self.directory_container_vec_deque.iter_mut() {
+                let maximum_number_of_visible_directory_items = std::cmp::max(
+                    height_of_row.saturating_sub(
+                        directory_container.get_number_of_rows_around_directory_items(),
+                    ),
+                    1,
+                );
+                directory_container.set_maximum_number_of_visible_directory_items(Some(
+                    maximum_number_of_visible_directory_items,
+                ));
+            }
+
+            self.apply_truncation_options();
+
+            // The number of rows only grows, so this ends after at most one pass per container
+            let new_number_of_rows = self.get_number_of_rows_of_directory_containers();
+
+            if new_number_of_rows <= number_of_rows {
+                break;
+            }
+
+            number_of_rows = new_number_of_rows;
+        }
+    }
+
+    fn get_number_of_rows_of_directory_containers(&self) -> usize {
+        let starting_index = self.get_starting_index();
+        let mut number_of_rows = 0;
         let mut start_and_end_iteration_tuple: (usize, usize) =
             self.update_start_and_end_iteration_tuple((starting_index, starting_index));
 
         while start_and_end_iteration_tuple.0 < self.directory_container_vec_deque.len() {
-            self.is_directory_container_wider_than_terminal(start_and_end_iteration_tuple.0);
-
-            self.print_one_row_of_directory_containers(start_and_end_iteration_tuple);
-
+            number_of_rows += 1;
             start_and_end_iteration_tuple =
                 self.update_start_and_end_iteration_tuple(start_and_end_iteration_tuple);
         }
+
+        number_of_rows
     }
 
-    fn is_directory_container_wider_than_terminal(&self, iterator: usize) {
-        if self.directory_container_vec_deque[iterator].get_total_width_of_directory_container()
-            > self.terminal_dimensions.0
+    /// Applies the configured truncation options, then truncates any directory container that is
+    /// still wider than the terminal
+    fn apply_truncation_options(&mut self) {
+        match self
+            .path_container_settings
+            .directory_container_settings
+            .truncation_options
+            .clone()
         {
-            println!(
-                "The directory '{}' is wider than the terminal width and cannot be output.",
-                self.directory_container_vec_deque[iterator].get_directory_name()
+            TruncationOptions::HorizontalFit => self.apply_horizontal_fit_truncation(),
+            truncation_options => {
+                self.apply_truncation_options_to_printed_directory_containers(0, truncation_options)
+            }
+        }
+
+        self.apply_terminal_width_limit();
+    }
+
+    /// Falls back through more aggressive truncation options for each directory container that is
+    /// wider than the terminal, ending with the largest constant name length that fits.  A
+    /// directory container that can't fit at all (such as one with a long directory name) is
+    /// clipped when rendering, by `Frame::clip_lines`.
+    fn apply_terminal_width_limit(&mut self) {
+        let terminal_width = self.terminal_dimensions.0;
+        let fits_in_terminal = |directory_container: &DirectoryContainer| {
+            directory_container.get_total_width_of_directory_container() <= terminal_width
+        };
+
+        for directory_container in self.directory_container_vec_deque.iter_mut() {
+            if fits_in_terminal(directory_container) {
+                continue;
+            }
+
+            let fallback_truncation_options = [
+                TruncationOptions::Outliers {
+                    should_include_truncated_text_indicator_in_length: true,
+                },
+                TruncationOptions::AverageFileNameLength {
+                    should_include_truncated_text_indicator_in_length: true,
+                },
+            ];
+
+            if fallback_truncation_options
+                .iter()
+                .any(|truncation_options| {
+                    directory_container.apply_truncation_settings_to_directory_container(
+                        truncation_options.clone(),
+                    );
+                    fits_in_terminal(directory_container)
+                })
+            {
+                continue;
+            }
+
+            // When even the shortest names don't fit, the rest of the directory container (such as
+            // its directory name) is what's too wide, so names only need to fit within that
+            directory_container.apply_truncation_settings_to_directory_container(
+                PathContainer::get_horizontal_fit_truncation_options(1),
+            );
+            let maximum_width = std::cmp::max(
+                terminal_width,
+                directory_container.get_total_width_of_directory_container(),
+            );
+            directory_container
+                .apply_truncation_settings_to_directory_container(TruncationOptions::NoTruncation);
+
+            let mut shortest_name_length_after_truncation = 1;
+            let mut longest_name_length_after_truncation =
+                std::cmp::max(directory_container.get_length_of_longest_file_name(), 1);
+
+            while shortest_name_length_after_truncation < longest_name_length_after_truncation {
+                let name_length_after_truncation = (shortest_name_length_after_truncation
+                    + longest_name_length_after_truncation)
+                    .div_ceil(2);
+
+                directory_container.apply_truncation_settings_to_directory_container(
+                    PathContainer::get_horizontal_fit_truncation_options(
+                        name_length_after_truncation,
+                    ),
+                );
+
+                if directory_container.get_total_width_of_directory_container() <= maximum_width {
+                    shortest_name_length_after_truncation = name_length_after_truncation;
+                } else {
+                    longest_name_length_after_truncation = name_length_after_truncation - 1;
+                }
+            }
+
+            directory_container.apply_truncation_settings_to_directory_container(
+                PathContainer::get_horizontal_fit_truncation_options(
+                    shortest_name_length_after_truncation,
+                ),
+            );
+        }
+    }
+
+    /// Finds the largest name length that all printed directory containers can be truncated to
+    /// while still fitting on a single row, and truncates them to it.  When they don't fit even
+    /// with a single character of each name left, they aren't truncated and wrap onto more rows.
+    fn apply_horizontal_fit_truncation(&mut self) {
+        if !matches!(
+            self.path_container_settings
+                .directory_container_settings
+                .truncation_options,
+            TruncationOptions::HorizontalFit
+        ) {
+            return;
+        }
+
+        let starting_index = self.get_starting_index();
+
+        self.apply_truncation_options_to_printed_directory_containers(
+            starting_index,
+            TruncationOptions::NoTruncation,
+        );
+
+        if self.can_fit_printed_directory_containers_in_row(starting_index) {
+            return;
+        }
+
+        let mut longest_name_length_after_truncation = self
+            .directory_container_vec_deque
+            .range(starting_index..)
+            .map(|directory_container| directory_container.get_length_of_longest_file_name())
+            .max()
+            .unwrap_or(1);
+
+        // Any shorter, and names would be nothing but the truncated text indicator
+        let shortest_useful_name_length_after_truncation = get_display_width(
+            &self
+                .path_container_settings
+                .directory_container_settings
+                .truncated_text_indicator,
+        ) + 1;
+
+        self.apply_truncation_options_to_printed_directory_containers(
+            starting_index,
+            PathContainer::get_horizontal_fit_truncation_options(
+                shortest_useful_name_length_after_truncation,
+            ),
+        );
+
+        if !self.can_fit_printed_directory_containers_in_row(starting_index) {
+            self.apply_truncation_options_to_printed_directory_containers(
+                starting_index,
+                TruncationOptions::NoTruncation,
+            );
+            return;
+        }
+
+        let mut shortest_name_length_after_truncation =
+            shortest_useful_name_length_after_truncation;
+
+        while shortest_name_length_after_truncation < longest_name_length_after_truncation {
+            let name_length_after_truncation = (shortest_name_length_after_truncation
+                + longest_name_length_after_truncation)
+                .div_ceil(2);
+
+            self.apply_truncation_options_to_printed_directory_containers(
+                starting_index,
+                PathContainer::get_horizontal_fit_truncation_options(name_length_after_truncation),
             );
-            std::process::exit(0);
+
+            if self.can_fit_printed_directory_containers_in_row(starting_index) {
+                shortest_name_length_after_truncation = name_length_after_truncation;
+            } else {
+                longest_name_length_after_truncation = name_length_after_truncation - 1;
+            }
+        }
+
+        self.apply_truncation_options_to_printed_directory_containers(
+            starting_index,
+            PathContainer::get_horizontal_fit_truncation_options(
+                shortest_name_length_after_truncation,
+            ),
+        );
+    }
+
+    fn get_horizontal_fit_truncation_options(
+        name_length_after_truncation: usize,
+    ) -> TruncationOptions {
+        TruncationOptions::Constant {
+            constant: name_length_after_truncation,
+            should_include_truncated_text_indicator_in_length: true,
+        }
+    }
+
+    fn apply_truncation_options_to_printed_directory_containers(
+        &mut self,
+        starting_index: usize,
+        truncation_options: TruncationOptions,
+    ) {
+        for directory_container in self
+            .directory_container_vec_deque
+            .range_mut(starting_index..)
+        {
+            directory_container
+                .apply_truncation_settings_to_directory_container(truncation_options.clone());
+        }
+    }
+
+    fn can_fit_printed_directory_containers_in_row(&self, starting_index: usize) -> bool {
+        let widths_of_directory_containers: usize = self
+            .directory_container_vec_deque
+            .range(starting_index..)
+            .map(|directory_container| directory_container.get_total_width_of_directory_container())
+            .sum();
+        let number_of_directory_containers =
+            self.directory_container_vec_deque.len() - starting_index;
+        let spaces_between_directory_containers = number_of_directory_containers.saturating_sub(1)
+            * self
+                .path_container_settings
+                .spaces_between_directory_containers;
+
+        widths_of_directory_containers + spaces_between_directory_containers
+            < self.terminal_dimensions.0
+    }
+
+    fn get_starting_index(&self) -> usize {
+        match self
+            .path_container_settings
+            .number_of_directory_containers_to_print_option
+        {
+            Some(number_of_directory_containers_to_print)
+                if number_of_directory_containers_to_print
+                    <= self.directory_container_vec_deque.len() =>
+            {
+                self.directory_container_vec_deque.len() - number_of_directory_containers_to_print
+            }
+            _ => 0,
+        }
+    }
+
+    /// Lays the path out for a terminal `width` columns wide and renders it without styles, so the
+    /// selected item and the directories in the path are marked with text instead.  The layout is
+    /// restored afterwards.
+    pub fn render_to_string(&mut self, width: usize) -> String {
+        let should_use_text_markers = self.theme.should_use_text_markers;
+        let terminal_dimensions = self.terminal_dimensions;
+        let scroll_offset_vec: Vec<usize> = self
+            .directory_container_vec_deque
+            .iter()
+            .map(DirectoryContainer::get_scroll_offset)
+            .collect();
+        self.set_should_use_text_markers(true);
+        self.set_terminal_dimensions((width, terminal_dimensions.1));
+
+        let rendered_path = self.render().get_string(false);
+
+        // Wrapping onto more rows can scroll the directory containers, so restore them as well
+        self.set_should_use_text_markers(should_use_text_markers);
+        self.set_terminal_dimensions(terminal_dimensions);
+
+        for (directory_container, scroll_offset) in self
+            .directory_container_vec_deque
+            .iter_mut()
+            .zip(scroll_offset_vec)
+        {
+            directory_container.set_scroll_offset(scroll_offset);
+        }
+
+        rendered_path
+    }
+
+    /// The text markers change the widths of the directory containers, so the path must be laid
+    /// out again afterwards
+    fn set_should_use_text_markers(&mut self, should_use_text_markers: bool) {
+        self.theme.should_use_text_markers = should_use_text_markers;
+
+        for directory_container in self.directory_container_vec_deque.iter_mut() {
+            directory_container.set_should_use_text_markers(should_use_text_markers);
+        }
+    }
+
+    pub fn render(&self) -> Frame {
+        let mut frame = Frame::new();
+        let starting_index = self.get_starting_index();
+
+        let mut start_and_end_iteration_tuple: (usize, usize) =
+            self.update_start_and_end_iteration_tuple((starting_index, starting_index));
+
+        while start_and_end_iteration_tuple.0 < self.directory_container_vec_deque.len() {
+            self.render_one_row_of_directory_containers(&mut frame, start_and_end_iteration_tuple);
+
+            start_and_end_iteration_tuple =
+                self.update_start_and_end_iteration_tuple(start_and_end_iteration_tuple);
         }
+
+        frame.clip_lines(
+            self.terminal_dimensions.0,
+            &self.path_container_settings.clipped_text_indicator,
+        );
+
+        frame
+    }
+
+    /// Renders the settings that can be changed while browsing, and the keys that change them, on
+    /// the last line of `frame`, which `render` leaves empty
+    pub fn render_status_line(&self, frame: &mut Frame) {
+        let directory_container_settings =
+            &self.path_container_settings.directory_container_settings;
+        let sort_settings = &directory_container_settings.sort_settings;
+        let sort_key_names: Vec<&str> = sort_settings
+            .sort_key_vec
+            .iter()
+            .map(SortKey::get_name)
+            .collect();
+
+        let status_line = format!(
+            "[s] sort: {}  [r] order: {}  [.] hidden files: {}  [t] truncation: {}",
+            sort_key_names.join(", "),
+            if sort_settings.should_sort_in_descending_order {
+                "descending"
+            } else {
+                "ascending"
+            },
+            if directory_container_settings.should_display_hidden_files {
+                "shown"
+            } else {
+                "hidden"
+            },
+            directory_container_settings.truncation_options.get_name()
+        );
+
+        frame.push_styled_text(&status_line, &self.theme.status_line_style);
+        frame.clip_lines(
+            self.terminal_dimensions.0,
+            &self.path_container_settings.clipped_text_indicator,
+        );
     }
 
     // REFACTOR
@@ -156,7 +717,12 @@ impl PathContainer {
             let at_end_of_directory_container_deque =
                 start_and_end_iteration_tuple.1 >= self.directory_container_vec_deque.len();
 
-            if can_fit_current_directory_containers_in_row && !at_end_of_directory_container_deque {
+            // A directory container that is too wide for the terminal gets a row of its own
+            let is_row_empty = start_and_end_iteration_tuple.0 == start_and_end_iteration_tuple.1;
+
+            if (can_fit_current_directory_containers_in_row || is_row_empty)
+                && !at_end_of_directory_container_deque
+            {
                 previous_directory_containers_space_requirement += self
                     .directory_container_vec_deque[start_and_end_iteration_tuple.1]
                     .get_total_width_of_directory_container()
@@ -170,7 +736,11 @@ impl PathContainer {
         }
     }
 
-    fn print_one_row_of_directory_containers(&self, start_and_end_iteration_tuple: (usize, usize)) {
+    fn render_one_row_of_directory_containers(
+        &self,
+        frame: &mut Frame,
+        start_and_end_iteration_tuple: (usize, usize),
+    ) {
         let height_of_tallest_container =
             self.get_height_of_tallest_directory_container_in_range(start_and_end_iteration_tuple);
 
@@ -181,24 +751,22 @@ impl PathContainer {
         {
             if i < height_of_tallest_container {
                 for j in start_and_end_iteration_tuple.0..start_and_end_iteration_tuple.1 {
-                    self.print_single_line_of_each_directory_container(
+                    self.render_single_line_of_each_directory_container(
+                        frame,
                         j,
                         i,
                         j < start_and_end_iteration_tuple.1 - 1,
                     );
                 }
 
-                println!();
-            } else if start_and_end_iteration_tuple.1 < self.directory_container_vec_deque.len() - 1
-            {
-                println!(
-                    "{}",
-                    make_repeated_char_string(
-                        self.path_container_settings
-                            .char_between_directory_container_rows,
-                        self.terminal_dimensions.0
-                    )
-                );
+                frame.push_line_break();
+            } else if start_and_end_iteration_tuple.1 < self.directory_container_vec_deque.len() {
+                frame.push_text(&make_repeated_char_string(
+                    self.path_container_settings
+                        .char_between_directory_container_rows,
+                    self.terminal_dimensions.0,
+                ));
+                frame.push_line_break();
             }
         }
     }
@@ -221,8 +789,9 @@ impl PathContainer {
         height_of_tallest_container
     }
 
-    fn print_single_line_of_each_directory_container(
+    fn render_single_line_of_each_directory_container(
         &self,
+        frame: &mut Frame,
         directory_container_number: usize,
         row_number: usize,
         should_print_spaces_between_directory_containers: bool,
@@ -232,27 +801,55 @@ impl PathContainer {
                 .get_total_height_of_directory_container()
         {
             self.directory_container_vec_deque[directory_container_number]
-                .print_directory_container_by_row(row_number);
+                .render_directory_container_row(frame, row_number);
         } else {
-            print!(
-                "{}",
-                make_repeated_char_string(
-                    self.path_container_settings.filler_char,
-                    self.directory_container_vec_deque[directory_container_number]
-                        .get_total_width_of_directory_container()
-                )
-            );
+            frame.push_text(&make_repeated_char_string(
+                self.path_container_settings.filler_char,
+                self.directory_container_vec_deque[directory_container_number]
+                    .get_total_width_of_directory_container(),
+            ));
         }
         if should_print_spaces_between_directory_containers {
-            print!(
-                "{}",
-                make_repeated_char_string(
-                    self.path_container_settings
-                        .char_between_directory_containers,
-                    self.path_container_settings
-                        .spaces_between_directory_containers
-                )
-            );
+            frame.push_text(&make_repeated_char_string(
+                self.path_container_settings
+                    .char_between_directory_containers,
+                self.path_container_settings
+                    .spaces_between_directory_containers,
+            ));
         }
     }
 }
+
+#[test]
+fn render_to_string_keeps_the_terminal_dimensions() {
+    let temporary_directory_path =
+        std::env::temp_dir().join(format!("termifind-render-to-string-{}", std::process::id()));
+    let directory_path = temporary_directory_path.join("z_directory");
+    std::fs::create_dir_all(&directory_path).unwrap();
+    std::fs::write(directory_path.join("a_long_file_name.txt"), "").unwrap();
+
+    // The selected directory is scrolled into view, and further when the narrow width wraps
+    for number in 0..20 {
+        std::fs::write(
+            temporary_directory_path.join(format!("file_{}", number)),
+            "",
+        )
+        .unwrap();
+    }
+
+    let settings = crate::settings::Settings::default();
+    let mut path_container = PathContainer::new(
+        directory_path,
+        settings.path_container_settings,
+        settings.theme,
+        (200, 16),
+    );
+
+    let rendered_path = path_container.render().get_string(false);
+    let narrow_rendered_path = path_container.render_to_string(20);
+    let rendered_path_afterwards = path_container.render().get_string(false);
+    std::fs::remove_dir_all(&temporary_directory_path).unwrap();
+
+    assert_ne!(narrow_rendered_path, rendered_path);
+    assert_eq!(rendered_path_afterwards, rendered_path);
+}
@@ -1,24 +1,46 @@
 use std::{
-    fs::{read_dir, ReadDir},
-    path::PathBuf,
+    fs::read_dir,
+    io,
+    path::{Path, PathBuf},
+    rc::Rc,
 };
 
 use average::Mean;
 use outliers::OutlierIdentifier;
 
-mod directory_item;
+pub mod directory_item;
 pub use directory_item::{DirectoryItem, ItemState, ItemType, NameTruncationSettings};
 
-use crate::settings::{DirectoryContainerSettings, TruncationOptions};
-use crate::utils::string::formatting::{add_padding_to_center_string, make_repeated_char_string};
+use crate::error::get_io_error_message;
+use crate::frame::Frame;
+use crate::git_repository::GitRepository;
+use crate::settings::{
+    DirectoryContainerSettings, HeightOptions, IgnoredFileOptions, MetadataColumn, ThemeSettings,
+    TruncationOptions,
+};
+use crate::utils::string::formatting::{
+    add_padding_to_center_string, get_display_width, make_repeated_char_string,
+};
+use crate::utils::string::sanitizing::escape_control_characters;
+
+const SCROLL_UP_INDICATOR_SYMBOL: char = '▲';
+const SCROLL_DOWN_INDICATOR_SYMBOL: char = '▼';
 
 pub struct DirectoryContainer {
     pub path_to_directory: PathBuf,
     pub directory_item_vec: Vec<DirectoryItem>,
     directory_name: String,
+    read_directory_error_option: Option<io::Error>,
+    number_of_unreadable_entries: usize, // Entries that failed to be read have no name to show
     minimum_width: usize,
+    metadata_column_width_vec: Vec<usize>,
+    git_status_indicator_width: usize,
+    maximum_number_of_visible_directory_items_option: Option<usize>,
+    scroll_offset: usize,
     name_truncation_settings_option: Option<NameTruncationSettings>,
+    git_repository_option: Option<Rc<GitRepository>>, // Set when inside a git work tree
     directory_container_settings: DirectoryContainerSettings,
+    theme: ThemeSettings,
 }
 
 impl DirectoryContainer {
@@ -26,68 +48,207 @@ impl DirectoryContainer {
         path: PathBuf,
         selected_directory_option: &Option<PathBuf>,
         directory_container_settings: DirectoryContainerSettings,
+        theme: ThemeSettings,
+        git_repository_option: Option<Rc<GitRepository>>,
     ) -> Self {
+        let (directory_item_vec, number_of_unreadable_entries, read_directory_error_option) =
+            DirectoryContainer::read_directory(
+                &path,
+                selected_directory_option,
+                &directory_container_settings,
+                &git_repository_option,
+            );
+
+        let directory_name: String = escape_control_characters(
+            &match path.file_name() {
+                Some(d_name) => d_name.to_string_lossy(),
+                None => path.to_string_lossy(),
+            },
+            &directory_container_settings
+                .directory_item_settings
+                .control_character_options,
+        );
+
+        let mut directory_container = DirectoryContainer {
+            directory_name,
+            read_directory_error_option,
+            number_of_unreadable_entries,
+            minimum_width: 0,
+            metadata_column_width_vec: Vec::new(),
+            git_status_indicator_width: 0,
+            maximum_number_of_visible_directory_items_option: None,
+            scroll_offset: 0,
+            path_to_directory: path,
+            directory_item_vec,
+            name_truncation_settings_option: None,
+            git_repository_option,
+            directory_container_settings,
+            theme,
+        };
+
+        directory_container.sort_directory_items();
+        directory_container.apply_truncation_settings_to_directory_container(
+            directory_container
+                .directory_container_settings
+                .truncation_options
+                .clone(),
+        );
+        directory_container.set_maximum_number_of_visible_directory_items(
+            match directory_container
+                .directory_container_settings
+                .height_options
+            {
+                HeightOptions::NoLimit => None,
+                HeightOptions::Constant { constant } => Some(constant),
+                // `PathContainer` applies this, as it depends on the terminal height
+                HeightOptions::TerminalHeight => None,
+            },
+        );
+
+        directory_container
+    }
+
+    /// Reads the directory items in `path`, marking the one at `selected_directory_option` as the
+    /// directory in the path.  That directory is kept even when it is a hidden or ignored file
+    /// that isn't displayed, so the path stays complete.  Entries that can't be read are only
+    /// counted.
+    fn read_directory(
+        path: &Path,
+        selected_directory_option: &Option<PathBuf>,
+        directory_container_settings: &DirectoryContainerSettings,
+        git_repository_option: &Option<Rc<GitRepository>>,
+    ) -> (Vec<DirectoryItem>, usize, Option<io::Error>) {
         let mut directory_item_vec: Vec<DirectoryItem> = Vec::new();
-        let read_directory_iterator: ReadDir = read_dir(&path).expect("Oops");
+        let mut number_of_unreadable_entries: usize = 0;
+
+        let read_directory_iterator = match read_dir(path) {
+            Ok(read_directory_iterator) => read_directory_iterator,
+            Err(io_error) => return (directory_item_vec, 0, Some(io_error)),
+        };
+
+        for directory_entry_result in read_directory_iterator {
+            let directory_entry = match directory_entry_result {
+                Ok(directory_entry) => directory_entry,
+                Err(_) => {
+                    number_of_unreadable_entries += 1;
+                    continue;
+                }
+            };
 
-        for file in read_directory_iterator {
             let mut directory_item: DirectoryItem = DirectoryItem::new(
-                file.expect("Oops"),
+                directory_entry,
                 directory_container_settings.directory_item_settings.clone(),
+                &directory_container_settings.metadata_column_settings,
             );
 
+            if let Some(selected_directory) = selected_directory_option {
+                if selected_directory == &directory_item.directory_entry.path() {
+                    directory_item.item_state = ItemState::DirectoryInPath;
+                }
+            }
+
+            let is_directory_in_path =
+                matches!(directory_item.item_state, ItemState::DirectoryInPath);
+
             if !directory_container_settings.should_display_hidden_files
                 && directory_item.is_hidden_file()
+                && !is_directory_in_path
             {
                 continue;
             }
 
-            if let Some(selected_directory) = selected_directory_option {
-                if selected_directory == &directory_item.directory_entry.path() {
-                    directory_item.item_state = ItemState::DirectoryInPath;
+            if let Some(git_repository) = git_repository_option {
+                let path = directory_item.directory_entry.path();
+
+                if directory_container_settings.should_display_git_status {
+                    directory_item.git_status_option = git_repository.get_git_status_option(&path);
+                }
+
+                if directory_container_settings.ignored_file_options != IgnoredFileOptions::Display
+                    && git_repository.is_ignored(&path, directory_item.is_directory())
+                {
+                    if directory_container_settings.ignored_file_options == IgnoredFileOptions::Hide
+                        && !is_directory_in_path
+                    {
+                        continue;
+                    }
+
+                    directory_item.is_ignored_file = true;
                 }
             }
 
             directory_item_vec.push(directory_item);
         }
 
-        let directory_name: String = match path.file_name() {
-            Some(d_name) => d_name.to_string_lossy().to_string(),
-            None => path.to_string_lossy().to_string(),
-        };
-
-        let mut directory_container = DirectoryContainer {
-            directory_name,
-            minimum_width: 0,
-            path_to_directory: path,
-            directory_item_vec,
-            name_truncation_settings_option: None,
-            directory_container_settings,
-        };
+        (directory_item_vec, number_of_unreadable_entries, None)
+    }
 
-        directory_container.sort_directory_items(
-            directory_container
-                .directory_container_settings
-                .sort_directory_item_by_item_type_indicator,
-        );
-        directory_container.apply_truncation_settings_to_directory_container(
-            directory_container
+    /// Applies settings changed while browsing: the directory is read again when hidden files are
+    /// toggled, then the directory items are sorted and truncated again.  The selected item stays
+    /// selected if it is still displayed, and the item at its index is selected otherwise.
+    pub fn set_directory_container_settings(
+        &mut self,
+        directory_container_settings: DirectoryContainerSettings,
+    ) {
+        let should_read_directory = directory_container_settings.should_display_hidden_files
+            != self
                 .directory_container_settings
-                .truncation_options
-                .clone(),
-        );
+                .should_display_hidden_files;
+        self.directory_container_settings = directory_container_settings;
+
+        if should_read_directory {
+            let selected_index_option = self.get_selected_directory_item_index_option();
+            let selected_path_option = self
+                .get_selected_directory_item()
+                .map(|directory_item| directory_item.directory_entry.path());
+            let directory_in_path_option = self
+                .directory_item_vec
+                .iter()
+                .find(|directory_item| {
+                    matches!(directory_item.item_state, ItemState::DirectoryInPath)
+                })
+                .map(|directory_item| directory_item.directory_entry.path());
+
+            let (directory_item_vec, number_of_unreadable_entries, read_directory_error_option) =
+                DirectoryContainer::read_directory(
+                    &self.path_to_directory,
+                    &directory_in_path_option,
+                    &self.directory_container_settings,
+                    &self.git_repository_option,
+                );
+            self.directory_item_vec = directory_item_vec;
+            self.number_of_unreadable_entries = number_of_unreadable_entries;
+            self.read_directory_error_option = read_directory_error_option;
+
+            if let Some(selected_index) = selected_index_option {
+                let index = self
+                    .directory_item_vec
+                    .iter()
+                    .position(|directory_item| {
+                        Some(directory_item.directory_entry.path()) == selected_path_option
+                    })
+                    .unwrap_or_else(|| {
+                        std::cmp::min(
+                            selected_index,
+                            self.directory_item_vec.len().saturating_sub(1),
+                        )
+                    });
+                self.select_directory_item_by_index(index);
+            }
+        }
 
-        directory_container
+        self.sort_directory_items();
+        self.apply_truncation_settings_to_directory_container(
+            self.directory_container_settings.truncation_options.clone(),
+        );
+        self.update_scroll_offset();
     }
 
-    fn sort_directory_items(&mut self, by_file_type: bool) {
-        let name_truncation_settings_option = &self.name_truncation_settings_option.clone();
+    fn sort_directory_items(&mut self) {
+        let sort_settings = &self.directory_container_settings.sort_settings;
 
-        self.directory_item_vec.sort_by(|a, b| {
-            a.get_file_name(by_file_type, &name_truncation_settings_option)
-                .partial_cmp(&b.get_file_name(by_file_type, &name_truncation_settings_option))
-                .expect("Oops")
-        });
+        self.directory_item_vec
+            .sort_by(|a, b| a.compare(b, sort_settings));
     }
 
     pub fn apply_truncation_settings_to_directory_container(
@@ -100,6 +261,9 @@ impl DirectoryContainer {
 
     // REFACTOR
     fn set_truncation_settings(&mut self, truncation_options: TruncationOptions) {
+        // The truncation values are calculated from the file name lengths, which must not be
+        // truncated by previously applied truncation options
+        self.name_truncation_settings_option = None;
         self.name_truncation_settings_option = match truncation_options {
             TruncationOptions::NoTruncation => None,
             TruncationOptions::Constant {
@@ -148,19 +312,23 @@ impl DirectoryContainer {
                 let outliers_result =
                     OutlierIdentifier::new(file_name_lengths_vec, true).get_outliers();
 
-                Some(NameTruncationSettings {
-                    name_length_after_truncation: self.get_truncation_value_by_level(
-                        outliers_result.expect("Oops").2.len(),
-                        false,
-                    ),
-                    should_include_truncated_text_indicator_in_length,
-                    truncated_text_indicator: self
-                        .directory_container_settings
-                        .truncated_text_indicator
-                        .clone(),
-                })
+                // Only fails on NaNs or a negative k value, neither of which can happen here
+                match outliers_result {
+                    Ok((_, _, upper_outliers)) => Some(NameTruncationSettings {
+                        name_length_after_truncation: self
+                            .get_truncation_value_by_level(upper_outliers.len(), false),
+                        should_include_truncated_text_indicator_in_length,
+                        truncated_text_indicator: self
+                            .directory_container_settings
+                            .truncated_text_indicator
+                            .clone(),
+                    }),
+                    Err(_) => None,
+                }
             }
-            TruncationOptions::HorizontalFit => None, // Implement
+            // `PathContainer` applies this as `Constant`, as it depends on the terminal width and
+            // every printed directory container
+            TruncationOptions::HorizontalFit => None,
         }
     }
 
@@ -173,13 +341,10 @@ impl DirectoryContainer {
 
         file_name_length_vec.reverse();
 
-        let vec_length = file_name_length_vec.len();
-
-        if level < vec_length {
-            return file_name_length_vec[level];
+        match file_name_length_vec.get(level) {
+            Some(file_name_length) => *file_name_length,
+            None => file_name_length_vec.last().copied().unwrap_or(0),
         }
-
-        file_name_length_vec[vec_length - 1]
     }
 
     fn get_truncated_value_by_file_name_average(&self) -> usize {
@@ -191,18 +356,211 @@ impl DirectoryContainer {
     }
 
     fn set_minimum_width(&mut self) {
+        self.set_metadata_column_widths();
+        self.git_status_indicator_width = self
+            .directory_item_vec
+            .iter()
+            .map(|directory_item| {
+                get_display_width(directory_item.get_git_status_indicator_string())
+            })
+            .max()
+            .unwrap_or(0);
+
         let length_of_longest_file_name: usize =
             match self.get_file_name_lengths_vec(true).iter().max() {
                 Some(x) => *x,
                 None => 0,
             };
-        let length_of_current_directory_name = self.directory_name.chars().count();
+        let length_of_longest_file_name = match length_of_longest_file_name {
+            0 => 0,
+            _ => {
+                length_of_longest_file_name
+                    + self.get_text_marker_width()
+                    + self.get_metadata_columns_width()
+                    + self.get_git_status_column_width()
+            }
+        };
+        let length_of_current_directory_name = get_display_width(&self.directory_name);
+        let length_of_longest_file_name = match self.get_error_message_option() {
+            Some(error_message) => std::cmp::max(
+                length_of_longest_file_name,
+                get_display_width(&error_message),
+            ),
+            None => length_of_longest_file_name,
+        };
 
         self.minimum_width = if length_of_current_directory_name > length_of_longest_file_name {
             length_of_current_directory_name
         } else {
             length_of_longest_file_name
         };
+
+        // Make room for the widest possible scroll indicator, so the width doesn't change while
+        // scrolling
+        if self.get_number_of_visible_directory_items() < self.get_number_of_directory_items() {
+            let length_of_longest_scroll_indicator =
+                get_display_width(&DirectoryContainer::get_scroll_indicator(
+                    SCROLL_DOWN_INDICATOR_SYMBOL,
+                    self.get_number_of_directory_items(),
+                ));
+
+            if self.minimum_width < length_of_longest_scroll_indicator {
+                self.minimum_width = length_of_longest_scroll_indicator;
+            }
+        }
+    }
+
+    pub fn set_maximum_number_of_visible_directory_items(
+        &mut self,
+        maximum_number_of_visible_directory_items_option: Option<usize>,
+    ) {
+        self.maximum_number_of_visible_directory_items_option =
+            maximum_number_of_visible_directory_items_option;
+        self.set_minimum_width();
+        self.update_scroll_offset();
+    }
+
+    pub fn get_scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    /// Still keeps the selected item, or the directory in the path, visible
+    pub fn set_scroll_offset(&mut self, scroll_offset: usize) {
+        self.scroll_offset = scroll_offset;
+        self.update_scroll_offset();
+    }
+
+    fn get_number_of_visible_directory_items(&self) -> usize {
+        match self.maximum_number_of_visible_directory_items_option {
+            Some(maximum_number_of_visible_directory_items) => std::cmp::min(
+                maximum_number_of_visible_directory_items,
+                self.get_number_of_directory_items(),
+            ),
+            None => self.get_number_of_directory_items(),
+        }
+    }
+
+    /// Scrolls just far enough to keep the selected item, or the directory in the path, visible
+    fn update_scroll_offset(&mut self) {
+        let number_of_visible_directory_items = self.get_number_of_visible_directory_items();
+        let focused_index_option = self.directory_item_vec.iter().position(|directory_item| {
+            matches!(
+                directory_item.item_state,
+                ItemState::Selected | ItemState::DirectoryInPath
+            )
+        });
+
+        if let Some(focused_index) = focused_index_option {
+            if focused_index < self.scroll_offset {
+                self.scroll_offset = focused_index;
+            } else if focused_index >= self.scroll_offset + number_of_visible_directory_items {
+                self.scroll_offset = focused_index + 1 - number_of_visible_directory_items;
+            }
+        }
+
+        self.scroll_offset = std::cmp::min(
+            self.scroll_offset,
+            self.get_number_of_directory_items() - number_of_visible_directory_items,
+        );
+    }
+
+    fn get_scroll_indicator(
+        scroll_indicator_symbol: char,
+        number_of_hidden_items: usize,
+    ) -> String {
+        format!(
+            " {} {} more ",
+            scroll_indicator_symbol, number_of_hidden_items
+        )
+    }
+
+    /// Each metadata column is as wide as its widest text in this directory container
+    fn set_metadata_column_widths(&mut self) {
+        self.metadata_column_width_vec = self
+            .directory_container_settings
+            .metadata_column_settings
+            .metadata_column_vec
+            .iter()
+            .enumerate()
+            .map(|(index, _)| {
+                self.directory_item_vec
+                    .iter()
+                    .map(|directory_item| {
+                        get_display_width(&directory_item.get_metadata_column_text_vec()[index])
+                    })
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+    }
+
+    /// The width of every metadata column, each followed by a space
+    fn get_metadata_columns_width(&self) -> usize {
+        self.metadata_column_width_vec
+            .iter()
+            .map(|metadata_column_width| metadata_column_width + 1)
+            .sum()
+    }
+
+    /// The git status indicators are only given a column when an item has one
+    fn get_git_status_column_width(&self) -> usize {
+        match self.git_status_indicator_width {
+            0 => 0,
+            git_status_indicator_width => git_status_indicator_width + 1,
+        }
+    }
+
+    fn render_git_status_column(&self, frame: &mut Frame, directory_item: &DirectoryItem) {
+        if self.git_status_indicator_width == 0 {
+            return;
+        }
+
+        directory_item.render_git_status_indicator(frame, &self.theme);
+        frame.push_text(&make_repeated_char_string(
+            ' ',
+            self.git_status_indicator_width
+                - get_display_width(directory_item.get_git_status_indicator_string())
+                + 1,
+        ));
+    }
+
+    /// Numbers are aligned to the right, like in `ls -l`, and everything else to the left
+    fn render_metadata_columns(&self, frame: &mut Frame, directory_item: &DirectoryItem) {
+        let metadata_column_iterator = self
+            .directory_container_settings
+            .metadata_column_settings
+            .metadata_column_vec
+            .iter()
+            .zip(directory_item.get_metadata_column_text_vec())
+            .zip(&self.metadata_column_width_vec);
+
+        for ((metadata_column, metadata_column_text), metadata_column_width) in
+            metadata_column_iterator
+        {
+            let padding = make_repeated_char_string(
+                ' ',
+                metadata_column_width - get_display_width(metadata_column_text),
+            );
+            let padded_metadata_column_text = match metadata_column {
+                MetadataColumn::LinkCount | MetadataColumn::Size => {
+                    format!("{}{}", padding, metadata_column_text)
+                }
+                _ => format!("{}{}", metadata_column_text, padding),
+            };
+
+            frame.push_styled_text(
+                &padded_metadata_column_text,
+                &self.theme.metadata_column_style,
+            );
+            frame.push_text(" ");
+        }
+    }
+
+    pub fn get_length_of_longest_file_name(&self) -> usize {
+        match self.get_file_name_lengths_vec(false).last() {
+            Some(x) => *x,
+            None => 0,
+        }
     }
 
     fn get_file_name_lengths_vec(&self, include_type_indicator_in_length: bool) -> Vec<usize> {
@@ -222,82 +580,281 @@ impl DirectoryContainer {
         file_name_vec
     }
 
-    pub fn print_directory_container_by_row(&self, row_number: usize) {
+    pub fn render_directory_container_row(&self, frame: &mut Frame, row_number: usize) {
         let beginning_directory_item_row = 3;
-        let ending_directory_item_row = self.get_total_height_of_directory_container() - 1;
+        let ending_directory_item_row =
+            beginning_directory_item_row + self.get_number_of_visible_directory_items();
 
         match row_number {
-            1 => self.print_directory_container_file_name_row(),
-            2 => self.print_content_divider_row(),
+            0 => self.render_horizontal_directory_container_line_row(frame, 0),
+            1 => self.render_directory_container_file_name_row(frame),
+            2 => self.render_content_divider_row(frame),
             x if (beginning_directory_item_row..ending_directory_item_row).contains(&x) => {
-                self.print_directory_item_row(row_number - beginning_directory_item_row);
+                self.render_directory_item_row(frame, row_number - beginning_directory_item_row);
+            }
+            x if x < self.get_total_height_of_directory_container() - 1 => {
+                self.render_error_message_row(frame)
             }
-            _ => self.print_horizontal_directory_container_line_row(),
+            _ => self.render_horizontal_directory_container_line_row(
+                frame,
+                self.get_number_of_directory_items()
+                    - self.scroll_offset
+                    - self.get_number_of_visible_directory_items(),
+            ),
         }
     }
 
-    fn print_horizontal_directory_container_line_row(&self) {
-        print!(
-            " {} ",
-            make_repeated_char_string(
-                self.directory_container_settings.horizontal_border_symbol,
-                self.minimum_width + 2
-            )
+    fn render_horizontal_directory_container_line_row(
+        &self,
+        frame: &mut Frame,
+        number_of_hidden_items: usize,
+    ) {
+        let horizontal_border_symbol = self.directory_container_settings.horizontal_border_symbol;
+
+        frame.push_text(" ");
+        frame.push_styled_text(
+            &match number_of_hidden_items {
+                0 => make_repeated_char_string(horizontal_border_symbol, self.minimum_width + 2),
+                _ => add_padding_to_center_string(
+                    &DirectoryContainer::get_scroll_indicator(
+                        SCROLL_DOWN_INDICATOR_SYMBOL,
+                        number_of_hidden_items,
+                    ),
+                    horizontal_border_symbol,
+                    self.minimum_width + 2,
+                ),
+            },
+            &self.theme.border_style,
         );
+        frame.push_text(" ");
     }
 
-    fn print_directory_container_file_name_row(&self) {
-        print!(
-            "{}{}{}",
-            self.directory_container_settings.vertical_border_symbol,
-            add_padding_to_center_string(
+    fn render_directory_container_file_name_row(&self, frame: &mut Frame) {
+        self.render_vertical_border_symbol(frame);
+        frame.push_styled_text(
+            &add_padding_to_center_string(
                 &self.directory_name,
                 self.directory_container_settings
                     .padding_symbol_to_center_directory_names,
-                self.minimum_width + 2
+                self.minimum_width + 2,
             ),
-            self.directory_container_settings.vertical_border_symbol,
+            &self.theme.directory_name_style,
         );
+        self.render_vertical_border_symbol(frame);
     }
 
-    fn print_content_divider_row(&self) {
-        print!(
-            "{}{}{}",
-            self.directory_container_settings.vertical_border_symbol,
-            make_repeated_char_string(
-                self.directory_container_settings.content_divider_symbol,
-                self.minimum_width + 2
-            ),
-            self.directory_container_settings.vertical_border_symbol,
+    fn render_content_divider_row(&self, frame: &mut Frame) {
+        let content_divider_symbol = self.directory_container_settings.content_divider_symbol;
+
+        self.render_vertical_border_symbol(frame);
+        frame.push_styled_text(
+            &match self.scroll_offset {
+                0 => make_repeated_char_string(content_divider_symbol, self.minimum_width + 2),
+                _ => add_padding_to_center_string(
+                    &DirectoryContainer::get_scroll_indicator(
+                        SCROLL_UP_INDICATOR_SYMBOL,
+                        self.scroll_offset,
+                    ),
+                    content_divider_symbol,
+                    self.minimum_width + 2,
+                ),
+            },
+            &self.theme.content_divider_style,
         );
+        self.render_vertical_border_symbol(frame);
     }
 
-    fn print_directory_item_row(&self, row_number: usize) {
-        print!(
-            "{} ",
-            self.directory_container_settings.vertical_border_symbol
-        );
+    /// Shows why the directory couldn't be read, or how many of its entries couldn't be
+    fn render_error_message_row(&self, frame: &mut Frame) {
+        let error_message = self.get_error_message_option().unwrap_or_default();
+        let difference: usize = self.minimum_width - get_display_width(&error_message);
 
-        let directory_item = &self.directory_item_vec[row_number];
-        directory_item.print_styled_file_name(true, &self.name_truncation_settings_option);
+        self.render_vertical_border_symbol(frame);
+        frame.push_text(" ");
+        frame.push_styled_text(&error_message, &self.theme.error_message_style);
+        frame.push_text(&make_repeated_char_string(' ', difference + 1));
+        self.render_vertical_border_symbol(frame);
+    }
+
+    fn render_directory_item_row(&self, frame: &mut Frame, row_number: usize) {
+        self.render_vertical_border_symbol(frame);
+        frame.push_text(" ");
+
+        let directory_item = &self.directory_item_vec[self.scroll_offset + row_number];
+        frame.push_text(&self.get_text_marker(directory_item));
+        self.render_metadata_columns(frame, directory_item);
+        self.render_git_status_column(frame, directory_item);
+        directory_item.render_styled_file_name(
+            frame,
+            true,
+            &self.name_truncation_settings_option,
+            &self.theme,
+        );
 
         let length_of_current_file_name: usize =
             directory_item.get_file_name_length(true, &self.name_truncation_settings_option);
-        let difference: usize = self.minimum_width - length_of_current_file_name;
+        let difference: usize = self.minimum_width
+            - self.get_text_marker_width()
+            - self.get_metadata_columns_width()
+            - self.get_git_status_column_width()
+            - length_of_current_file_name;
+
+        frame.push_text(&make_repeated_char_string(' ', difference + 1));
+        self.render_vertical_border_symbol(frame);
+    }
+
+    /// Without colors, the selected item, the directory in the path and dimmed ignored files are
+    /// marked with text.  Every item gets a marker of the same width, so names stay aligned.
+    fn get_text_marker(&self, directory_item: &DirectoryItem) -> String {
+        if !self.theme.should_use_text_markers {
+            return String::new();
+        }
+
+        let text_marker = match directory_item.item_state {
+            ItemState::DirectoryInPath => self.theme.directory_in_path_text_marker.as_str(),
+            ItemState::Selected => self.theme.selected_text_marker.as_str(),
+            ItemState::Unselected if directory_item.is_ignored_file => {
+                self.theme.ignored_file_text_marker.as_str()
+            }
+            ItemState::Unselected => "",
+        };
+
+        format!(
+            "{}{}",
+            text_marker,
+            make_repeated_char_string(
+                ' ',
+                self.get_text_marker_width() - get_display_width(text_marker)
+            )
+        )
+    }
+
+    pub fn set_should_use_text_markers(&mut self, should_use_text_markers: bool) {
+        self.theme.should_use_text_markers = should_use_text_markers;
+    }
+
+    fn get_text_marker_width(&self) -> usize {
+        if !self.theme.should_use_text_markers {
+            return 0;
+        }
 
-        print!(
-            "{} {}",
-            make_repeated_char_string(' ', difference),
-            self.directory_container_settings.vertical_border_symbol
+        // Leave a space between the marker and the item type indicator
+        [
+            &self.theme.directory_in_path_text_marker,
+            &self.theme.selected_text_marker,
+            &self.theme.ignored_file_text_marker,
+        ]
+        .iter()
+        .map(|text_marker| get_display_width(text_marker))
+        .max()
+        .unwrap_or(0)
+            + 1
+    }
+
+    fn render_vertical_border_symbol(&self, frame: &mut Frame) {
+        frame.push_styled_text(
+            &self
+                .directory_container_settings
+                .vertical_border_symbol
+                .to_string(),
+            &self.theme.border_style,
         );
     }
 
+    pub fn get_selected_directory_item(&self) -> Option<&DirectoryItem> {
+        self.directory_item_vec
+            .iter()
+            .find(|directory_item| matches!(directory_item.item_state, ItemState::Selected))
+    }
+
+    pub fn get_selected_directory_item_mut(&mut self) -> Option<&mut DirectoryItem> {
+        self.directory_item_vec
+            .iter_mut()
+            .find(|directory_item| matches!(directory_item.item_state, ItemState::Selected))
+    }
+
+    fn get_selected_directory_item_index_option(&self) -> Option<usize> {
+        self.directory_item_vec
+            .iter()
+            .position(|directory_item| matches!(directory_item.item_state, ItemState::Selected))
+    }
+
+    fn select_directory_item_by_index(&mut self, index: usize) {
+        if let Some(selected_directory_item) = self.get_selected_directory_item_mut() {
+            selected_directory_item.item_state = ItemState::Unselected;
+        }
+
+        if let Some(directory_item) = self.directory_item_vec.get_mut(index) {
+            directory_item.item_state = ItemState::Selected;
+        }
+    }
+
+    pub fn select_previous_directory_item(&mut self) {
+        if let Some(index) = self.get_selected_directory_item_index_option() {
+            if index > 0 {
+                self.select_directory_item_by_index(index - 1);
+                self.update_scroll_offset();
+            }
+        }
+    }
+
+    pub fn select_next_directory_item(&mut self) {
+        if let Some(index) = self.get_selected_directory_item_index_option() {
+            if index + 1 < self.directory_item_vec.len() {
+                self.select_directory_item_by_index(index + 1);
+                self.update_scroll_offset();
+            }
+        }
+    }
+
+    pub fn select_directory_item_in_path(&mut self) {
+        if let Some(directory_item) = self
+            .directory_item_vec
+            .iter_mut()
+            .find(|directory_item| matches!(directory_item.item_state, ItemState::DirectoryInPath))
+        {
+            directory_item.item_state = ItemState::Selected;
+        }
+
+        self.update_scroll_offset();
+    }
+
+    pub fn select_first_directory_item(&mut self) {
+        self.select_directory_item_by_index(0);
+        self.update_scroll_offset();
+    }
+
     pub fn get_total_width_of_directory_container(&self) -> usize {
         self.minimum_width + 4
     }
 
     pub fn get_total_height_of_directory_container(&self) -> usize {
-        self.get_number_of_directory_items() + 4
+        self.get_number_of_visible_directory_items()
+            + self.get_number_of_rows_around_directory_items()
+    }
+
+    /// The borders, the directory name and the divider below it, and the error row, if any
+    pub fn get_number_of_rows_around_directory_items(&self) -> usize {
+        // Errors use a single row below the directory items
+        match self.get_error_message_option() {
+            Some(_) => 5,
+            None => 4,
+        }
+    }
+
+    fn get_error_message_option(&self) -> Option<String> {
+        if let Some(read_directory_error) = &self.read_directory_error_option {
+            return Some(get_io_error_message(read_directory_error));
+        }
+
+        match self.number_of_unreadable_entries {
+            0 => None,
+            1 => Some(String::from("1 entry could not be read")),
+            number_of_unreadable_entries => Some(format!(
+                "{} entries could not be read",
+                number_of_unreadable_entries
+            )),
+        }
     }
 
     pub fn get_number_of_directory_items(&self) -> usize {
@@ -308,3 +865,48 @@ impl DirectoryContainer {
         &self.directory_name
     }
 }
+
+#[test]
+fn render_number_of_unreadable_entries() {
+    let temporary_directory_path = std::env::temp_dir().join(format!(
+        "termifind-unreadable-entries-{}",
+        std::process::id()
+    ));
+    let directory_path = temporary_directory_path.join("directory");
+    std::fs::create_dir_all(&directory_path).unwrap();
+    std::fs::write(directory_path.join("readable.txt"), "").unwrap();
+
+    let mut settings = crate::settings::Settings::default();
+    settings.theme.should_use_text_markers = true;
+    let mut directory_container = DirectoryContainer::new(
+        directory_path,
+        &None,
+        settings
+            .path_container_settings
+            .directory_container_settings,
+        settings.theme,
+        None,
+    );
+    directory_container.number_of_unreadable_entries = 2;
+    directory_container.set_minimum_width();
+
+    let mut frame = Frame::new();
+    for row_number in 0..directory_container.get_total_height_of_directory_container() {
+        directory_container.render_directory_container_row(&mut frame, row_number);
+        frame.push_line_break();
+    }
+    std::fs::remove_dir_all(&temporary_directory_path).unwrap();
+
+    assert_eq!(
+        frame.get_lines(false),
+        [
+            " ----------------------------- ",
+            "|          directory          |",
+            "|=============================|",
+            "|   (F) readable.txt          |",
+            "| 2 entries could not be read |",
+            " ----------------------------- ",
+            "",
+        ]
+    );
+}
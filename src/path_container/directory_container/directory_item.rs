@@ -1,9 +1,27 @@
-use std::fs::DirEntry;
+use std::{
+    cmp::Ordering,
+    fs::{metadata, read_link, symlink_metadata, DirEntry, Metadata},
+    io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use chrono::{DateTime, Local};
 
 use crate::{
-    settings::DirectoryItemSettings,
-    utils::string::formatting::truncate_text,
-    utils::string::styling::{print_colored_text, Color},
+    error::get_io_error_message,
+    frame::Frame,
+    git_repository::GitStatus,
+    settings::{
+        DirectoryItemSettings, LsColors, MetadataColumn, MetadataColumnSettings, SortKey,
+        SortSettings, ThemeSettings, TimeOptions,
+    },
+    utils::string::formatting::{
+        format_relative_time, format_size, get_display_width, truncate_text,
+    },
+    utils::string::sanitizing::escape_control_characters,
+    utils::string::sorting::compare_naturally,
+    utils::string::styling::Style,
 };
 
 pub enum ItemState {
@@ -12,10 +30,19 @@ pub enum ItemState {
     Unselected,
 }
 
+// Sorting by item type follows the order of the variants
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
 pub enum ItemType {
     Directory,
     File,
-    Symlink,
+    ExecutableFile,
+    NamedPipe,
+    Socket,
+    BlockDevice,
+    CharacterDevice,
+    SymlinkToDirectory,
+    SymlinkToFile,
+    BrokenSymlink,
     Unknown,
 }
 
@@ -29,34 +56,248 @@ pub struct NameTruncationSettings {
 pub struct DirectoryItem {
     pub directory_entry: DirEntry,
     pub item_state: ItemState,
+    pub git_status_option: Option<GitStatus>,
+    pub is_ignored_file: bool, // Only set when ignored files are dimmed
     item_type: ItemType,
+    symlink_target_option: Option<PathBuf>,
+    metadata_option: Option<Metadata>, // Of the symlink itself, like `ls -l`
+    metadata_error_option: Option<io::Error>,
+    metadata_column_text_vec: Vec<String>,
     directory_item_settings: DirectoryItemSettings,
 }
 
 impl DirectoryItem {
-    pub fn new(directory_entry: DirEntry, directory_item_settings: DirectoryItemSettings) -> Self {
-        let item_type = DirectoryItem::get_item_type(&directory_entry);
+    pub fn new(
+        directory_entry: DirEntry,
+        directory_item_settings: DirectoryItemSettings,
+        metadata_column_settings: &MetadataColumnSettings,
+    ) -> Self {
+        let path = directory_entry.path();
+        let (item_type, symlink_target_option, metadata_option, metadata_error_option) =
+            match symlink_metadata(&path) {
+                Ok(symlink_metadata) if symlink_metadata.file_type().is_symlink() => (
+                    DirectoryItem::get_symlink_type(&path),
+                    read_link(&path).ok(),
+                    Some(symlink_metadata),
+                    None,
+                ),
+                Ok(symlink_metadata) => (
+                    DirectoryItem::get_item_type(&symlink_metadata),
+                    None,
+                    Some(symlink_metadata),
+                    None,
+                ),
+                Err(io_error) => (ItemType::Unknown, None, None, Some(io_error)),
+            };
 
-        DirectoryItem {
+        let mut directory_item = DirectoryItem {
             item_state: ItemState::Unselected,
+            git_status_option: None,
+            is_ignored_file: false,
             directory_entry,
             item_type,
+            symlink_target_option,
+            metadata_option,
+            metadata_error_option,
+            metadata_column_text_vec: Vec::new(),
             directory_item_settings,
+        };
+
+        directory_item.metadata_column_text_vec = metadata_column_settings
+            .metadata_column_vec
+            .iter()
+            .map(|metadata_column| {
+                directory_item.get_metadata_column_text(metadata_column, metadata_column_settings)
+            })
+            .collect();
+
+        directory_item
+    }
+
+    /// The text of each column in `MetadataColumnSettings::metadata_column_vec`, which is
+    /// formatted once, when the directory is read
+    pub fn get_metadata_column_text_vec(&self) -> &[String] {
+        &self.metadata_column_text_vec
+    }
+
+    /// Returns `?` for everything when the metadata couldn't be read, like `ls -l`
+    fn get_metadata_column_text(
+        &self,
+        metadata_column: &MetadataColumn,
+        metadata_column_settings: &MetadataColumnSettings,
+    ) -> String {
+        let metadata = match &self.metadata_option {
+            Some(metadata) => metadata,
+            None => return String::from("?"),
+        };
+
+        match metadata_column {
+            MetadataColumn::Permissions => DirectoryItem::get_permission_string(metadata),
+            MetadataColumn::LinkCount => DirectoryItem::get_link_count_string(metadata),
+            MetadataColumn::Owner => DirectoryItem::get_owner_name(metadata),
+            MetadataColumn::Group => DirectoryItem::get_group_name(metadata),
+            MetadataColumn::Size => {
+                format_size(metadata.len(), &metadata_column_settings.size_options)
+            }
+            MetadataColumn::ModificationTime => match metadata.modified() {
+                Ok(modification_time) => match &metadata_column_settings.time_options {
+                    TimeOptions::Absolute { time_format } => {
+                        DateTime::<Local>::from(modification_time)
+                            .format(time_format)
+                            .to_string()
+                    }
+                    // A time in the future (such as from clock skew) counts as just now
+                    TimeOptions::Relative => format_relative_time(
+                        SystemTime::now()
+                            .duration_since(modification_time)
+                            .unwrap_or_default(),
+                    ),
+                },
+                Err(_) => String::from("?"),
+            },
         }
     }
 
-    fn get_item_type(directory_entry: &DirEntry) -> ItemType {
-        match directory_entry.metadata() {
-            Ok(metadata) => {
-                if metadata.is_dir() {
-                    ItemType::Directory
-                } else if metadata.is_file() {
-                    ItemType::File
-                } else {
-                    ItemType::Symlink
-                }
+    #[cfg(unix)]
+    fn get_permission_string(metadata: &Metadata) -> String {
+        use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+        let file_type = metadata.file_type();
+        let mode = metadata.permissions().mode();
+        let mut permission_string = String::from(if file_type.is_dir() {
+            "d"
+        } else if file_type.is_symlink() {
+            "l"
+        } else if file_type.is_fifo() {
+            "p"
+        } else if file_type.is_socket() {
+            "s"
+        } else if file_type.is_block_device() {
+            "b"
+        } else if file_type.is_char_device() {
+            "c"
+        } else {
+            "-"
+        });
+
+        // Owner, group and others, each with the special bit that replaces its execute bit
+        for (shift, special_bit, special_char) in
+            [(6, 0o4000, 's'), (3, 0o2000, 's'), (0, 0o1000, 't')].iter()
+        {
+            let permission_bits = mode >> shift;
+            permission_string.push(if permission_bits & 0o4 != 0 { 'r' } else { '-' });
+            permission_string.push(if permission_bits & 0o2 != 0 { 'w' } else { '-' });
+            permission_string.push(
+                match (permission_bits & 0o1 != 0, mode & special_bit != 0) {
+                    (true, true) => *special_char,
+                    (false, true) => special_char.to_ascii_uppercase(),
+                    (true, false) => 'x',
+                    (false, false) => '-',
+                },
+            );
+        }
+
+        permission_string
+    }
+
+    #[cfg(not(unix))]
+    fn get_permission_string(metadata: &Metadata) -> String {
+        String::from(if metadata.permissions().readonly() {
+            "r-"
+        } else {
+            "rw"
+        })
+    }
+
+    #[cfg(unix)]
+    fn get_link_count_string(metadata: &Metadata) -> String {
+        use std::os::unix::fs::MetadataExt;
+
+        metadata.nlink().to_string()
+    }
+
+    #[cfg(not(unix))]
+    fn get_link_count_string(_metadata: &Metadata) -> String {
+        String::from("?")
+    }
+
+    /// Falls back to the user ID when it has no name
+    #[cfg(unix)]
+    fn get_owner_name(metadata: &Metadata) -> String {
+        use std::os::unix::fs::MetadataExt;
+
+        uzers::get_user_by_uid(metadata.uid()).map_or_else(
+            || metadata.uid().to_string(),
+            |user| user.name().to_string_lossy().into_owned(),
+        )
+    }
+
+    #[cfg(not(unix))]
+    fn get_owner_name(_metadata: &Metadata) -> String {
+        String::from("?")
+    }
+
+    /// Falls back to the group ID when it has no name
+    #[cfg(unix)]
+    fn get_group_name(metadata: &Metadata) -> String {
+        use std::os::unix::fs::MetadataExt;
+
+        uzers::get_group_by_gid(metadata.gid()).map_or_else(
+            || metadata.gid().to_string(),
+            |group| group.name().to_string_lossy().into_owned(),
+        )
+    }
+
+    #[cfg(not(unix))]
+    fn get_group_name(_metadata: &Metadata) -> String {
+        String::from("?")
+    }
+
+    #[cfg(unix)]
+    fn get_item_type(symlink_metadata: &Metadata) -> ItemType {
+        use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+        let file_type = symlink_metadata.file_type();
+
+        if file_type.is_dir() {
+            ItemType::Directory
+        } else if file_type.is_file() {
+            // Executable by anyone
+            if symlink_metadata.permissions().mode() & 0o111 != 0 {
+                ItemType::ExecutableFile
+            } else {
+                ItemType::File
             }
-            Err(_) => ItemType::Unknown,
+        } else if file_type.is_fifo() {
+            ItemType::NamedPipe
+        } else if file_type.is_socket() {
+            ItemType::Socket
+        } else if file_type.is_block_device() {
+            ItemType::BlockDevice
+        } else if file_type.is_char_device() {
+            ItemType::CharacterDevice
+        } else {
+            ItemType::Unknown
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn get_item_type(symlink_metadata: &Metadata) -> ItemType {
+        if symlink_metadata.is_dir() {
+            ItemType::Directory
+        } else if symlink_metadata.is_file() {
+            ItemType::File
+        } else {
+            ItemType::Unknown
+        }
+    }
+
+    /// Follows the symlink at `path` to find out what it points to
+    fn get_symlink_type(path: &Path) -> ItemType {
+        match metadata(path) {
+            Ok(target_metadata) if target_metadata.is_dir() => ItemType::SymlinkToDirectory,
+            Ok(_) => ItemType::SymlinkToFile,
+            Err(_) => ItemType::BrokenSymlink,
         }
     }
 
@@ -67,13 +308,14 @@ impl DirectoryItem {
     ) -> String {
         if should_include_item_type_indicator {
             return format!(
-                "{} {}",
+                "{} {}{}",
                 self.get_item_type_indicator_string(),
-                self.get_truncated_file_name(&name_truncation_settings_option)
+                self.get_truncated_file_name(name_truncation_settings_option),
+                self.get_file_name_annotations()
             );
         }
 
-        self.get_truncated_file_name(&name_truncation_settings_option)
+        self.get_truncated_file_name(name_truncation_settings_option)
     }
 
     pub fn get_file_name_length(
@@ -81,58 +323,177 @@ impl DirectoryItem {
         should_include_item_type_indicator_in_length: bool,
         name_truncation_settings_option: &Option<NameTruncationSettings>,
     ) -> usize {
-        self.get_file_name(
+        get_display_width(&self.get_file_name(
             should_include_item_type_indicator_in_length,
-            &name_truncation_settings_option,
-        )
-        .chars()
-        .count()
+            name_truncation_settings_option,
+        ))
     }
 
-    pub fn print_styled_file_name(
+    pub fn render_styled_file_name(
         &self,
+        frame: &mut Frame,
         should_include_item_type_indicator: bool,
         name_truncation_settings_option: &Option<NameTruncationSettings>,
+        theme: &ThemeSettings,
     ) {
-        let file_name = self.get_file_name(
-            should_include_item_type_indicator,
-            &name_truncation_settings_option,
+        frame.push_styled_text(
+            &self.get_file_name(
+                should_include_item_type_indicator,
+                name_truncation_settings_option,
+            ),
+            self.get_style(theme),
         );
+    }
 
+    fn get_style<'a>(&self, theme: &'a ThemeSettings) -> &'a Style {
         match self.item_state {
-            ItemState::DirectoryInPath => print_colored_text(file_name, Color::Blue),
-            ItemState::Selected => print_colored_text(file_name, Color::Green),
-            ItemState::Unselected => match self.item_type {
-                ItemType::Directory => print_colored_text(file_name, Color::White),
-                ItemType::File => print_colored_text(file_name, Color::Magenta),
-                ItemType::Symlink => print_colored_text(file_name, Color::Red),
-                ItemType::Unknown => print_colored_text(file_name, Color::Cyan),
-            },
+            ItemState::DirectoryInPath => &theme.directory_in_path_style,
+            ItemState::Selected => &theme.selected_style,
+            ItemState::Unselected => {
+                if self.is_ignored_file {
+                    return &theme.ignored_file_style;
+                }
+
+                if let Some(style) = theme
+                    .ls_colors_option
+                    .as_ref()
+                    .and_then(|ls_colors| self.get_ls_colors_style(ls_colors))
+                {
+                    return style;
+                }
+
+                match self.item_type {
+                    ItemType::Directory => &theme.directory_style,
+                    ItemType::File => &theme.file_style,
+                    ItemType::ExecutableFile => &theme.executable_file_style,
+                    ItemType::NamedPipe => &theme.named_pipe_style,
+                    ItemType::Socket => &theme.socket_style,
+                    ItemType::BlockDevice => &theme.block_device_style,
+                    ItemType::CharacterDevice => &theme.character_device_style,
+                    ItemType::SymlinkToDirectory => &theme.symlink_to_directory_style,
+                    ItemType::SymlinkToFile => &theme.symlink_style,
+                    ItemType::BrokenSymlink => &theme.broken_symlink_style,
+                    ItemType::Unknown => &theme.unknown_style,
+                }
+            }
+        }
+    }
+
+    /// Follows the order `ls` uses: symlinks and special files by type, executables by `ex`, and
+    /// only then regular files (and executables without `ex`) by their `*.ext` pattern or `fi`
+    fn get_ls_colors_style<'a>(&self, ls_colors: &'a LsColors) -> Option<&'a Style> {
+        let get_regular_file_style = || {
+            ls_colors
+                .get_file_name_style(&self.directory_entry.file_name().to_string_lossy())
+                .or_else(|| ls_colors.get_file_type_style("fi"))
         };
+
+        match self.item_type {
+            ItemType::Directory => ls_colors.get_file_type_style("di"),
+            ItemType::File => get_regular_file_style(),
+            ItemType::ExecutableFile => ls_colors
+                .get_file_type_style("ex")
+                .or_else(get_regular_file_style),
+            ItemType::NamedPipe => ls_colors.get_file_type_style("pi"),
+            ItemType::Socket => ls_colors.get_file_type_style("so"),
+            ItemType::BlockDevice => ls_colors.get_file_type_style("bd"),
+            ItemType::CharacterDevice => ls_colors.get_file_type_style("cd"),
+            ItemType::SymlinkToDirectory if ls_colors.should_style_symlinks_as_target() => {
+                ls_colors.get_file_type_style("di")
+            }
+            ItemType::SymlinkToFile if ls_colors.should_style_symlinks_as_target() => {
+                get_regular_file_style()
+            }
+            ItemType::SymlinkToDirectory | ItemType::SymlinkToFile => {
+                ls_colors.get_file_type_style("ln")
+            }
+            ItemType::BrokenSymlink => ls_colors
+                .get_file_type_style("or")
+                .or_else(|| ls_colors.get_file_type_style("ln")),
+            ItemType::Unknown => None,
+        }
     }
 
     fn get_item_type_indicator_string(&self) -> &str {
         match self.item_type {
             ItemType::Directory => &self.directory_item_settings.item_type_indicator_directory,
             ItemType::File => &self.directory_item_settings.item_type_indicator_file,
-            ItemType::Symlink => &self.directory_item_settings.item_type_indicator_symlink,
+            ItemType::ExecutableFile => {
+                &self
+                    .directory_item_settings
+                    .item_type_indicator_executable_file
+            }
+            ItemType::NamedPipe => &self.directory_item_settings.item_type_indicator_named_pipe,
+            ItemType::Socket => &self.directory_item_settings.item_type_indicator_socket,
+            ItemType::BlockDevice => {
+                &self
+                    .directory_item_settings
+                    .item_type_indicator_block_device
+            }
+            ItemType::CharacterDevice => {
+                &self
+                    .directory_item_settings
+                    .item_type_indicator_character_device
+            }
+            ItemType::SymlinkToDirectory => {
+                &self
+                    .directory_item_settings
+                    .item_type_indicator_symlink_to_directory
+            }
+            ItemType::SymlinkToFile => &self.directory_item_settings.item_type_indicator_symlink,
+            ItemType::BrokenSymlink => {
+                &self
+                    .directory_item_settings
+                    .item_type_indicator_broken_symlink
+            }
             ItemType::Unknown => &self.directory_item_settings.item_type_indicator_unknown,
         }
     }
 
+    /// Empty for items without a git status
+    pub fn get_git_status_indicator_string(&self) -> &str {
+        match self.git_status_option {
+            Some(GitStatus::Modified) => {
+                &self.directory_item_settings.git_status_indicator_modified
+            }
+            Some(GitStatus::Staged) => &self.directory_item_settings.git_status_indicator_staged,
+            Some(GitStatus::Untracked) => {
+                &self.directory_item_settings.git_status_indicator_untracked
+            }
+            Some(GitStatus::Ignored) => &self.directory_item_settings.git_status_indicator_ignored,
+            Some(GitStatus::Conflicted) => {
+                &self.directory_item_settings.git_status_indicator_conflicted
+            }
+            None => "",
+        }
+    }
+
+    pub fn render_git_status_indicator(&self, frame: &mut Frame, theme: &ThemeSettings) {
+        let style = match self.git_status_option {
+            Some(GitStatus::Modified) => &theme.git_modified_style,
+            Some(GitStatus::Staged) => &theme.git_staged_style,
+            Some(GitStatus::Untracked) => &theme.git_untracked_style,
+            Some(GitStatus::Ignored) => &theme.git_ignored_style,
+            Some(GitStatus::Conflicted) => &theme.git_conflicted_style,
+            None => return,
+        };
+
+        frame.push_styled_text(self.get_git_status_indicator_string(), style);
+    }
+
     fn get_truncated_file_name(
         &self,
         name_truncation_settings_option: &Option<NameTruncationSettings>,
     ) -> String {
-        let file_name = self
-            .directory_entry
-            .file_name()
-            .to_string_lossy()
-            .to_string();
+        let file_name = self.directory_entry.file_name();
+        let mut truncated_file_name = escape_control_characters(
+            &file_name.to_string_lossy(),
+            &self.directory_item_settings.control_character_options,
+        );
 
         if let Some(name_truncation_settings) = name_truncation_settings_option {
-            return truncate_text(
-                file_name,
+            truncated_file_name = truncate_text(
+                truncated_file_name,
                 name_truncation_settings.name_length_after_truncation,
                 Some((
                     name_truncation_settings.truncated_text_indicator.clone(),
@@ -141,7 +502,124 @@ impl DirectoryItem {
             );
         }
 
-        file_name
+        truncated_file_name
+    }
+
+    /// Returns the text printed after the (possibly truncated) file name, which is never truncated
+    fn get_file_name_annotations(&self) -> String {
+        let mut file_name_annotations = String::new();
+
+        // Invalid UTF-8 has been replaced with U+FFFD, so make it clear this isn't the real name
+        if self.directory_entry.file_name().to_str().is_none() {
+            file_name_annotations.push(' ');
+            file_name_annotations.push_str(&self.directory_item_settings.invalid_unicode_indicator);
+        }
+
+        if self.directory_item_settings.should_display_symlink_target {
+            if let Some(symlink_target) = &self.symlink_target_option {
+                file_name_annotations.push_str(" -> ");
+                file_name_annotations.push_str(&escape_control_characters(
+                    &symlink_target.to_string_lossy(),
+                    &self.directory_item_settings.control_character_options,
+                ));
+            }
+        }
+
+        if let Some(metadata_error) = &self.metadata_error_option {
+            file_name_annotations.push_str(&format!(" ({})", get_io_error_message(metadata_error)));
+        }
+
+        file_name_annotations
+    }
+
+    /// Compares on the real file name and metadata, never on the rendered (and possibly truncated)
+    /// name.  Items without metadata sort as if they were empty and as old as possible.
+    pub fn compare(&self, other: &DirectoryItem, sort_settings: &SortSettings) -> Ordering {
+        if sort_settings.should_sort_directories_first {
+            let ordering = other.is_directory().cmp(&self.is_directory());
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+
+        let ordering = sort_settings
+            .sort_key_vec
+            .iter()
+            .map(|sort_key| self.compare_by_sort_key(other, sort_key))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or_else(|| self.compare_by_sort_key(other, &SortKey::Name));
+
+        if sort_settings.should_sort_in_descending_order {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+
+    fn compare_by_sort_key(&self, other: &DirectoryItem, sort_key: &SortKey) -> Ordering {
+        let file_name = self.directory_entry.file_name();
+        let other_file_name = other.directory_entry.file_name();
+
+        match sort_key {
+            SortKey::Name => file_name.cmp(&other_file_name),
+            SortKey::NaturalName => compare_naturally(
+                &file_name.to_string_lossy(),
+                &other_file_name.to_string_lossy(),
+            ),
+            SortKey::CaseInsensitiveName => file_name
+                .to_string_lossy()
+                .to_lowercase()
+                .cmp(&other_file_name.to_string_lossy().to_lowercase()),
+            SortKey::Extension => Path::new(&file_name)
+                .extension()
+                .cmp(&Path::new(&other_file_name).extension()),
+            SortKey::Size => self.get_size().cmp(&other.get_size()),
+            SortKey::ModificationTime => self
+                .get_modification_time_option()
+                .cmp(&other.get_modification_time_option()),
+            SortKey::ChangeTime => self
+                .get_change_time_option()
+                .cmp(&other.get_change_time_option()),
+            SortKey::ItemType => self.item_type.cmp(&other.item_type),
+        }
+    }
+
+    pub fn get_size(&self) -> u64 {
+        self.metadata_option
+            .as_ref()
+            .map_or(0, |metadata| metadata.len())
+    }
+
+    pub fn get_modification_time_option(&self) -> Option<SystemTime> {
+        self.metadata_option
+            .as_ref()
+            .and_then(|metadata| metadata.modified().ok())
+    }
+
+    #[cfg(unix)]
+    pub fn get_change_time_option(&self) -> Option<SystemTime> {
+        use std::{convert::TryFrom, os::unix::fs::MetadataExt, time::Duration};
+
+        let metadata = self.metadata_option.as_ref()?;
+        let seconds = u64::try_from(metadata.ctime()).ok()?;
+        let nanoseconds = u32::try_from(metadata.ctime_nsec()).ok()?;
+
+        Some(SystemTime::UNIX_EPOCH + Duration::new(seconds, nanoseconds))
+    }
+
+    #[cfg(not(unix))]
+    pub fn get_change_time_option(&self) -> Option<SystemTime> {
+        self.metadata_option
+            .as_ref()
+            .and_then(|metadata| metadata.created().ok())
+    }
+
+    pub fn is_directory(&self) -> bool {
+        matches!(
+            self.item_type,
+            ItemType::Directory | ItemType::SymlinkToDirectory
+        )
     }
 
     pub fn is_hidden_file(&self) -> bool {
@@ -1,8 +1,30 @@
-use serde_json::from_str;
-use std::{fs::read_to_string, path::Path};
+use chrono::format::{Item, StrftimeItems};
+use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+use serde_json::{error::Category, Deserializer, Value};
+use std::{
+    env, fmt,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+use unicode_width::UnicodeWidthChar;
+
+use crate::utils::string::styling::{Color, Style};
+
+// Replaced by `sort_settings`; `true` sorts by `[ItemType, Name]`
+const DEPRECATED_SORT_BY_ITEM_TYPE_KEY: &str = "sort_directory_item_by_item_type_indicator";
+
+mod ls_colors;
+pub use ls_colors::LsColors;
+
+mod settings_error;
+pub use settings_error::SettingsError;
+
+const SETTINGS_PATH_ENVIRONMENT_VARIABLE: &str = "TERMIFIND_CONFIG";
+const SETTINGS_DIRECTORY_NAME: &str = "termifind";
+const SETTINGS_FILE_NAME: &str = "settings.json";
 
 #[allow(dead_code)]
-#[derive(serde_derive::Deserialize, Clone)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone)]
 pub enum TruncationOptions {
     NoTruncation,
     Constant {
@@ -22,29 +44,283 @@ pub enum TruncationOptions {
     HorizontalFit, // Performs calculations and then uses Constant
 }
 
-#[derive(serde_derive::Deserialize, Clone)]
+impl TruncationOptions {
+    /// The option that switching truncation options live moves on to.  `Constant` and `Level`
+    /// options from the settings move on to `HorizontalFit`, as the cycle only has `Level` 1.
+    pub fn get_next(&self) -> TruncationOptions {
+        let should_include_truncated_text_indicator_in_length = match self {
+            TruncationOptions::Constant {
+                should_include_truncated_text_indicator_in_length,
+                ..
+            }
+            | TruncationOptions::Level {
+                should_include_truncated_text_indicator_in_length,
+                ..
+            }
+            | TruncationOptions::AverageFileNameLength {
+                should_include_truncated_text_indicator_in_length,
+            }
+            | TruncationOptions::Outliers {
+                should_include_truncated_text_indicator_in_length,
+            } => *should_include_truncated_text_indicator_in_length,
+            TruncationOptions::NoTruncation | TruncationOptions::HorizontalFit => true,
+        };
+
+        match self {
+            TruncationOptions::Outliers { .. } => TruncationOptions::AverageFileNameLength {
+                should_include_truncated_text_indicator_in_length,
+            },
+            TruncationOptions::AverageFileNameLength { .. } => TruncationOptions::Level {
+                level: 1,
+                should_include_truncated_text_indicator_in_length,
+            },
+            TruncationOptions::Level { .. } | TruncationOptions::Constant { .. } => {
+                TruncationOptions::HorizontalFit
+            }
+            TruncationOptions::HorizontalFit => TruncationOptions::NoTruncation,
+            TruncationOptions::NoTruncation => TruncationOptions::Outliers {
+                should_include_truncated_text_indicator_in_length,
+            },
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            TruncationOptions::NoTruncation => String::from("NoTruncation"),
+            TruncationOptions::Constant { constant, .. } => format!("Constant {}", constant),
+            TruncationOptions::Level { level, .. } => format!("Level {}", level),
+            TruncationOptions::AverageFileNameLength { .. } => {
+                String::from("AverageFileNameLength")
+            }
+            TruncationOptions::Outliers { .. } => String::from("Outliers"),
+            TruncationOptions::HorizontalFit => String::from("HorizontalFit"),
+        }
+    }
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone)]
+pub enum HeightOptions {
+    NoLimit,
+    Constant { constant: usize }, // Maximum number of directory items shown at once
+    TerminalHeight,
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone, PartialEq)]
+pub enum IgnoredFileOptions {
+    Display,
+    Dim,  // Styled with `ignored_file_style`
+    Hide, // Unless they are in the current path, like hidden files
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone)]
+pub enum ControlCharacterOptions {
+    Caret,        // ^[
+    Hexadecimal,  // \x1b
+    QuestionMark, // ?
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum SortKey {
+    Name,                // Byte order of the real file name
+    NaturalName,         // Runs of digits compare by their value: file2 < file10
+    CaseInsensitiveName, // Name < name2 < NAME3
+    Extension,           // Names without an extension come first
+    Size,
+    ModificationTime,
+    ChangeTime, // The time the metadata last changed (ctime) on Unix, the creation time elsewhere
+    ItemType,   // In the order of the item type indicators in `DirectoryItemSettings`
+}
+
+impl SortKey {
+    /// The key that cycling the sort key live moves on to
+    pub fn get_next(&self) -> SortKey {
+        match self {
+            SortKey::Name => SortKey::NaturalName,
+            SortKey::NaturalName => SortKey::CaseInsensitiveName,
+            SortKey::CaseInsensitiveName => SortKey::Extension,
+            SortKey::Extension => SortKey::Size,
+            SortKey::Size => SortKey::ModificationTime,
+            SortKey::ModificationTime => SortKey::ChangeTime,
+            SortKey::ChangeTime => SortKey::ItemType,
+            SortKey::ItemType => SortKey::Name,
+        }
+    }
+
+    /// The name shown in the status line
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            SortKey::Name => "name",
+            SortKey::NaturalName => "natural name",
+            SortKey::CaseInsensitiveName => "case-insensitive name",
+            SortKey::Extension => "extension",
+            SortKey::Size => "size",
+            SortKey::ModificationTime => "modification time",
+            SortKey::ChangeTime => "change time",
+            SortKey::ItemType => "item type",
+        }
+    }
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct SortSettings {
+    pub sort_key_vec: Vec<SortKey>, // Later keys break ties, and names break any that remain
+    pub should_sort_in_descending_order: bool,
+    pub should_sort_directories_first: bool, // Applies in either order
+}
+
+impl Default for SortSettings {
+    fn default() -> Self {
+        SortSettings {
+            sort_key_vec: vec![SortKey::Name],
+            should_sort_in_descending_order: false,
+            should_sort_directories_first: false,
+        }
+    }
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone, Copy, PartialEq)]
+pub enum MetadataColumn {
+    Permissions, // drwxr-xr-x
+    LinkCount,
+    Owner,
+    Group,
+    Size,
+    ModificationTime,
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone)]
+pub enum SizeOptions {
+    Bytes, // 1536
+    Iec,   // 1.5Ki
+    Si,    // 1.5k
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone)]
+pub enum TimeOptions {
+    Absolute { time_format: String }, // In local time, with `strftime` specifiers like `%Y-%m-%d`
+    Relative,                         // 5 minutes ago
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetadataColumnSettings {
+    pub metadata_column_vec: Vec<MetadataColumn>, // Printed in this order, before the file name
+    pub size_options: SizeOptions,
+    pub time_options: TimeOptions,
+}
+
+impl Default for MetadataColumnSettings {
+    fn default() -> Self {
+        MetadataColumnSettings {
+            metadata_column_vec: Vec::new(),
+            size_options: SizeOptions::Iec,
+            time_options: TimeOptions::Absolute {
+                time_format: String::from("%Y-%m-%d %H:%M"),
+            },
+        }
+    }
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone)]
+#[serde(default, deny_unknown_fields)]
 pub struct DirectoryItemSettings {
     pub item_type_indicator_directory: String,
     pub item_type_indicator_file: String,
+    pub item_type_indicator_executable_file: String,
+    pub item_type_indicator_named_pipe: String,
+    pub item_type_indicator_socket: String,
+    pub item_type_indicator_block_device: String,
+    pub item_type_indicator_character_device: String,
     pub item_type_indicator_symlink: String,
+    pub item_type_indicator_symlink_to_directory: String,
+    pub item_type_indicator_broken_symlink: String,
     pub item_type_indicator_unknown: String,
+    pub git_status_indicator_modified: String,
+    pub git_status_indicator_staged: String,
+    pub git_status_indicator_untracked: String,
+    pub git_status_indicator_ignored: String,
+    pub git_status_indicator_conflicted: String,
+    pub should_display_symlink_target: bool,
+    pub control_character_options: ControlCharacterOptions,
+    pub invalid_unicode_indicator: String,
+}
+
+impl Default for DirectoryItemSettings {
+    fn default() -> Self {
+        DirectoryItemSettings {
+            item_type_indicator_directory: String::from("(D)"),
+            item_type_indicator_file: String::from("(F)"),
+            item_type_indicator_executable_file: String::from("(X)"),
+            item_type_indicator_named_pipe: String::from("(P)"),
+            item_type_indicator_socket: String::from("(SO)"),
+            item_type_indicator_block_device: String::from("(B)"),
+            item_type_indicator_character_device: String::from("(C)"),
+            item_type_indicator_symlink: String::from("(S)"),
+            item_type_indicator_symlink_to_directory: String::from("(SD)"),
+            item_type_indicator_broken_symlink: String::from("(SB)"),
+            item_type_indicator_unknown: String::from("(U)"),
+            git_status_indicator_modified: String::from("M"),
+            git_status_indicator_staged: String::from("S"),
+            git_status_indicator_untracked: String::from("?"),
+            git_status_indicator_ignored: String::from("!"),
+            git_status_indicator_conflicted: String::from("U"),
+            should_display_symlink_target: false,
+            control_character_options: ControlCharacterOptions::Caret,
+            invalid_unicode_indicator: String::from("(!)"),
+        }
+    }
 }
 
-#[derive(serde_derive::Deserialize, Clone)]
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone)]
+#[serde(default, deny_unknown_fields)]
 pub struct DirectoryContainerSettings {
-    pub sort_directory_item_by_item_type_indicator: bool,
+    pub sort_settings: SortSettings,
     pub should_display_hidden_files: bool,
+    pub should_display_git_status: bool, // Only read from the local `.git` directory
+    pub ignored_file_options: IgnoredFileOptions, // Only inside git work trees
     pub truncation_options: TruncationOptions,
     pub truncated_text_indicator: String,
+    pub height_options: HeightOptions,
     pub horizontal_border_symbol: char,
     pub vertical_border_symbol: char,
     pub content_divider_symbol: char,
     pub padding_symbol_to_center_directory_names: char,
 
+    pub metadata_column_settings: MetadataColumnSettings,
     pub directory_item_settings: DirectoryItemSettings,
+
+    // Only accepted so older settings files still load; `get_settings` moves it into
+    // `sort_settings`
+    #[serde(skip_serializing)]
+    sort_directory_item_by_item_type_indicator: Option<bool>,
 }
 
-#[derive(serde_derive::Deserialize)]
+impl Default for DirectoryContainerSettings {
+    fn default() -> Self {
+        DirectoryContainerSettings {
+            sort_settings: SortSettings::default(),
+            should_display_hidden_files: false,
+            should_display_git_status: false,
+            ignored_file_options: IgnoredFileOptions::Display,
+            truncation_options: TruncationOptions::Outliers {
+                should_include_truncated_text_indicator_in_length: true,
+            },
+            truncated_text_indicator: String::from("..."),
+            height_options: HeightOptions::TerminalHeight,
+            horizontal_border_symbol: '-',
+            vertical_border_symbol: '|',
+            content_divider_symbol: '=',
+            padding_symbol_to_center_directory_names: ' ',
+            metadata_column_settings: MetadataColumnSettings::default(),
+            directory_item_settings: DirectoryItemSettings::default(),
+            sort_directory_item_by_item_type_indicator: None,
+        }
+    }
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct PathContainerSettings {
     pub number_of_directory_containers_to_print_option: Option<usize>,
     pub spaces_between_directory_containers: usize,
@@ -52,58 +328,647 @@ pub struct PathContainerSettings {
     pub spaces_between_directory_container_rows: usize,
     pub char_between_directory_container_rows: char,
     pub filler_char: char,
+    pub clipped_text_indicator: String, // Ends rows that are still wider than the terminal
 
     pub directory_container_settings: DirectoryContainerSettings,
 }
 
-#[derive(serde_derive::Deserialize)]
-pub struct Settings {
-    pub path_container_settings: PathContainerSettings,
-}
-
-fn get_default_settings() -> Settings {
-    Settings {
-        path_container_settings: PathContainerSettings {
+impl Default for PathContainerSettings {
+    fn default() -> Self {
+        PathContainerSettings {
             number_of_directory_containers_to_print_option: None,
             spaces_between_directory_containers: 1,
             char_between_directory_containers: ' ',
             spaces_between_directory_container_rows: 1,
             char_between_directory_container_rows: ' ',
             filler_char: ' ',
-            directory_container_settings: DirectoryContainerSettings {
-                sort_directory_item_by_item_type_indicator: false,
-                should_display_hidden_files: false,
-                truncation_options: {
-                    TruncationOptions::Outliers {
-                        should_include_truncated_text_indicator_in_length: true,
-                    }
-                },
-                truncated_text_indicator: String::from("..."),
-                horizontal_border_symbol: '-',
-                vertical_border_symbol: '|',
-                content_divider_symbol: '=',
-                padding_symbol_to_center_directory_names: ' ',
-                directory_item_settings: DirectoryItemSettings {
-                    item_type_indicator_directory: String::from("(D)"),
-                    item_type_indicator_file: String::from("(F)"),
-                    item_type_indicator_symlink: String::from("(S)"),
-                    item_type_indicator_unknown: String::from("(U)"),
-                },
+            clipped_text_indicator: String::from("..."),
+            directory_container_settings: DirectoryContainerSettings::default(),
+        }
+    }
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeSettings {
+    // Item states take precedence over item types
+    pub directory_in_path_style: Style,
+    pub selected_style: Style,
+
+    pub directory_style: Style,
+    pub file_style: Style,
+    pub executable_file_style: Style,
+    pub named_pipe_style: Style,
+    pub socket_style: Style,
+    pub block_device_style: Style,
+    pub character_device_style: Style,
+    pub symlink_style: Style,
+    pub symlink_to_directory_style: Style,
+    pub broken_symlink_style: Style,
+    pub unknown_style: Style,
+
+    pub border_style: Style,
+    pub directory_name_style: Style,
+    pub content_divider_style: Style,
+    pub error_message_style: Style,
+    pub metadata_column_style: Style,
+    pub git_modified_style: Style,
+    pub git_staged_style: Style,
+    pub git_untracked_style: Style,
+    pub git_ignored_style: Style,
+    pub git_conflicted_style: Style,
+    pub ignored_file_style: Style,
+    pub status_line_style: Style, // Only shown while browsing interactively
+
+    // Item types are styled by `LS_COLORS` when it is set, falling back to the styles above
+    pub should_use_ls_colors: bool,
+    #[serde(skip)]
+    pub ls_colors_option: Option<LsColors>,
+
+    // Used instead of the item state styles when colors are disabled
+    pub directory_in_path_text_marker: String,
+    pub selected_text_marker: String,
+    pub ignored_file_text_marker: String, // Only when ignored files are dimmed
+    #[serde(skip)]
+    pub should_use_text_markers: bool,
+}
+
+impl Default for ThemeSettings {
+    fn default() -> Self {
+        ThemeSettings {
+            directory_in_path_style: Style::with_foreground_color(Color::Blue),
+            selected_style: Style::with_foreground_color(Color::Green),
+            directory_style: Style::with_foreground_color(Color::White),
+            file_style: Style::with_foreground_color(Color::Magenta),
+            executable_file_style: Style::with_foreground_color(Color::BrightGreen),
+            named_pipe_style: Style::with_foreground_color(Color::BrightYellow),
+            socket_style: Style::with_foreground_color(Color::BrightMagenta),
+            block_device_style: Style::with_foreground_color(Color::BrightCyan),
+            character_device_style: Style::with_foreground_color(Color::BrightBlue),
+            symlink_style: Style::with_foreground_color(Color::Red),
+            symlink_to_directory_style: Style::with_foreground_color(Color::Yellow),
+            broken_symlink_style: Style::with_foreground_color(Color::BrightRed),
+            unknown_style: Style::with_foreground_color(Color::Cyan),
+            border_style: Style::default(),
+            directory_name_style: Style::default(),
+            content_divider_style: Style::default(),
+            error_message_style: Style::with_foreground_color(Color::Red),
+            metadata_column_style: Style::default(),
+            git_modified_style: Style::with_foreground_color(Color::Yellow),
+            git_staged_style: Style::with_foreground_color(Color::Green),
+            git_untracked_style: Style::with_foreground_color(Color::Red),
+            git_ignored_style: Style::with_foreground_color(Color::BrightBlack),
+            git_conflicted_style: Style {
+                is_bold: true,
+                ..Style::with_foreground_color(Color::BrightRed)
+            },
+            ignored_file_style: Style {
+                is_dimmed: true,
+                ..Style::default()
             },
-        },
+            status_line_style: Style {
+                is_reversed: true,
+                ..Style::default()
+            },
+            should_use_ls_colors: false,
+            ls_colors_option: None,
+            directory_in_path_text_marker: String::from("*"),
+            selected_text_marker: String::from(">"),
+            ignored_file_text_marker: String::from("~"),
+            should_use_text_markers: false,
+        }
     }
 }
 
-pub fn get_settings() -> Settings {
-    // WILL NEED TO USE A CRATE TO FIND A PLACE TO STORE THE SETTINGS FILE THAT IS CROSS COMPATIBLE
-    let path_to_json_settings_file =
-        Path::new("/Users/josephlyons/Programming/Rust/termifind/termifind_settings.json");
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub path_container_settings: PathContainerSettings,
+    pub theme: ThemeSettings,
+}
+
+/// Finds the system-wide settings file: the first `termifind/settings.json` that exists in the
+/// directories listed in `$XDG_CONFIG_DIRS` (`/etc/xdg` if unset).
+fn get_system_settings_path_option() -> Option<PathBuf> {
+    let xdg_config_dirs = env::var("XDG_CONFIG_DIRS")
+        .ok()
+        .filter(|xdg_config_dirs| !xdg_config_dirs.is_empty())
+        .unwrap_or_else(|| String::from("/etc/xdg"));
 
-    if let Ok(json_settings_string) = read_to_string(path_to_json_settings_file) {
-        if let Ok(settings) = from_str(&json_settings_string) {
-            return settings;
+    xdg_config_dirs
+        .split(':')
+        .map(Path::new)
+        .filter(|config_directory| config_directory.is_absolute())
+        .map(|config_directory| {
+            config_directory
+                .join(SETTINGS_DIRECTORY_NAME)
+                .join(SETTINGS_FILE_NAME)
+        })
+        .find(|settings_path| settings_path.is_file())
+}
+
+/// Finds the user settings file, in order of precedence: the `--config` path, the
+/// `TERMIFIND_CONFIG` environment variable, `$XDG_CONFIG_HOME/termifind/settings.json`, and
+/// `~/.config/termifind/settings.json`.  The first two are used even if the file does not exist,
+/// while the last two are only used if the file exists.
+fn get_user_settings_path_option(config_path_option: &Option<PathBuf>) -> Option<PathBuf> {
+    if let Some(config_path) = config_path_option {
+        return Some(config_path.clone());
+    }
+
+    if let Some(settings_path) = env::var_os(SETTINGS_PATH_ENVIRONMENT_VARIABLE) {
+        if !settings_path.is_empty() {
+            return Some(PathBuf::from(settings_path));
         }
     }
 
-    get_default_settings()
+    let xdg_config_home_option = env::var_os("XDG_CONFIG_HOME")
+        .filter(|xdg_config_home| Path::new(xdg_config_home).is_absolute())
+        .map(PathBuf::from);
+    let home_config_option = env::var_os("HOME").map(|home| Path::new(&home).join(".config"));
+
+    xdg_config_home_option
+        .into_iter()
+        .chain(home_config_option)
+        .map(|config_directory| {
+            config_directory
+                .join(SETTINGS_DIRECTORY_NAME)
+                .join(SETTINGS_FILE_NAME)
+        })
+        .find(|settings_path| settings_path.is_file())
+}
+
+/// Returns the settings files to load, in the order they are layered: the system-wide file first,
+/// then the user file.
+pub fn get_settings_paths(config_path_option: &Option<PathBuf>) -> Vec<PathBuf> {
+    get_system_settings_path_option()
+        .into_iter()
+        .chain(get_user_settings_path_option(config_path_option))
+        .collect()
+}
+
+/// Loads every settings file in order, layering each one over the default settings and the files
+/// before it.  Any subset of keys may be given in a file; missing keys keep their previous value.
+pub fn get_settings(settings_paths: &[PathBuf]) -> Result<Settings, SettingsError> {
+    let mut merged_settings_value =
+        serde_json::to_value(Settings::default()).expect("Default settings are serializable");
+
+    for settings_path in settings_paths {
+        let json_settings_string =
+            read_to_string(settings_path).map_err(|io_error| SettingsError::Read {
+                settings_path: settings_path.clone(),
+                io_error,
+            })?;
+
+        // Deserialize each file on its own first, so errors point into the file they came from
+        let settings: Settings =
+            serde_path_to_error::deserialize(&mut Deserializer::from_str(&json_settings_string))
+                .map_err(|path_to_error| {
+                    let key_path = path_to_error.path().to_string();
+                    let serde_json_error = path_to_error.inner();
+
+                    SettingsError::Parse {
+                        settings_path: settings_path.clone(),
+                        line: serde_json_error.line(),
+                        column: serde_json_error.column(),
+                        key_path_option: match serde_json_error.classify() {
+                            Category::Syntax | Category::Eof => None,
+                            _ => Some(key_path),
+                        },
+                        // serde_json appends the position to its messages, which is already reported
+                        message: serde_json_error
+                            .to_string()
+                            .split(" at line ")
+                            .next()
+                            .unwrap_or_default()
+                            .to_string(),
+                    }
+                })?;
+
+        validate_settings(&settings).map_err(|(key_path, message)| SettingsError::Invalid {
+            settings_path: settings_path.clone(),
+            position_option: get_value_position(&json_settings_string, key_path),
+            key_path: String::from(key_path),
+            message,
+        })?;
+
+        let mut settings_value: Value = serde_json::from_str(&json_settings_string)
+            .expect("Settings that deserialize are valid JSON");
+        replace_deprecated_settings(&mut settings_value);
+        merge_settings_values(&mut merged_settings_value, settings_value);
+    }
+
+    let mut settings: Settings = serde_json::from_value(merged_settings_value)
+        .expect("Settings merged from valid settings are valid");
+
+    if settings.theme.should_use_ls_colors {
+        settings.theme.ls_colors_option = LsColors::from_environment();
+    }
+
+    Ok(settings)
+}
+
+/// Finds the line and column of the value at `key_path` (keys separated by `.`), where
+/// serde_json would report an error for it, by reading `json_settings_string` again and failing
+/// at that value
+fn get_value_position(json_settings_string: &str, key_path: &str) -> Option<(usize, usize)> {
+    let key_vec: Vec<&str> = key_path.split('.').collect();
+
+    match KeyPathSeed(&key_vec).deserialize(&mut Deserializer::from_str(json_settings_string)) {
+        Ok(()) => None,
+        Err(serde_json_error) => Some((serde_json_error.line(), serde_json_error.column())),
+    }
+}
+
+/// Skips every value outside of the key path, and fails at the value at its end, as `Visitor`
+/// rejects every type by default
+struct KeyPathSeed<'a>(&'a [&'a str]);
+
+impl<'de> DeserializeSeed<'de> for KeyPathSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeyPathSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "the key path")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map_access: A) -> Result<(), A::Error> {
+        let (key, remaining_key_vec) = match self.0.split_first() {
+            Some(key_and_remaining_key_vec) => key_and_remaining_key_vec,
+            None => return Err(serde::de::Error::custom("found the value")),
+        };
+
+        while let Some(map_key) = map_access.next_key::<String>()? {
+            if map_key == *key {
+                map_access.next_value_seed(KeyPathSeed(remaining_key_vec))?;
+            } else {
+                map_access.next_value::<IgnoredAny>()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Replaces deprecated keys with the settings that replaced them, unless those are also given
+fn replace_deprecated_settings(settings_value: &mut Value) {
+    let directory_container_settings_map = match settings_value
+        .pointer_mut("/path_container_settings/directory_container_settings")
+    {
+        Some(Value::Object(directory_container_settings_map)) => directory_container_settings_map,
+        _ => return,
+    };
+
+    if directory_container_settings_map.remove(DEPRECATED_SORT_BY_ITEM_TYPE_KEY)
+        == Some(Value::Bool(true))
+    {
+        if let Value::Object(sort_settings_map) = directory_container_settings_map
+            .entry("sort_settings")
+            .or_insert_with(|| Value::Object(serde_json::Map::new()))
+        {
+            sort_settings_map
+                .entry("sort_key_vec")
+                .or_insert_with(|| serde_json::json!(["ItemType", "Name"]));
+        }
+    }
+}
+
+/// Recursively merges `overlay` into `base`.  Objects are only merged key by key when every key of
+/// `overlay` already exists in `base`; otherwise (such as when switching to a different
+/// `TruncationOptions` variant), the value in `overlay` replaces the one in `base`.
+fn merge_settings_values(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base_map), Value::Object(overlay_map))
+            if overlay_map.keys().all(|key| base_map.contains_key(key)) =>
+        {
+            for (key, overlay_value) in overlay_map {
+                if let Some(base_value) = base_map.get_mut(&key) {
+                    merge_settings_values(base_value, overlay_value);
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn validate_settings(settings: &Settings) -> Result<(), (&'static str, String)> {
+    let path_container_settings = &settings.path_container_settings;
+    let directory_container_settings = &path_container_settings.directory_container_settings;
+
+    // Every symbol is repeated to draw lines of a known width, so each must occupy exactly one
+    // terminal column
+    let symbols = [
+        (
+            "path_container_settings.char_between_directory_containers",
+            path_container_settings.char_between_directory_containers,
+        ),
+        (
+            "path_container_settings.char_between_directory_container_rows",
+            path_container_settings.char_between_directory_container_rows,
+        ),
+        (
+            "path_container_settings.filler_char",
+            path_container_settings.filler_char,
+        ),
+        (
+            "path_container_settings.directory_container_settings.horizontal_border_symbol",
+            directory_container_settings.horizontal_border_symbol,
+        ),
+        (
+            "path_container_settings.directory_container_settings.vertical_border_symbol",
+            directory_container_settings.vertical_border_symbol,
+        ),
+        (
+            "path_container_settings.directory_container_settings.content_divider_symbol",
+            directory_container_settings.content_divider_symbol,
+        ),
+        (
+            "path_container_settings.directory_container_settings.padding_symbol_to_center_directory_names",
+            directory_container_settings.padding_symbol_to_center_directory_names,
+        ),
+    ];
+
+    for (key_path, symbol) in symbols.iter() {
+        if symbol.width() != Some(1) {
+            return Err((
+                key_path,
+                format!(
+                    "{:?} must be a character that is exactly one column wide",
+                    symbol
+                ),
+            ));
+        }
+    }
+
+    if directory_container_settings
+        .truncated_text_indicator
+        .is_empty()
+    {
+        return Err((
+            "path_container_settings.directory_container_settings.truncated_text_indicator",
+            String::from("must not be empty"),
+        ));
+    }
+
+    if let HeightOptions::Constant { constant: 0 } = directory_container_settings.height_options {
+        return Err((
+            "path_container_settings.directory_container_settings.height_options",
+            String::from("must show at least one directory item"),
+        ));
+    }
+
+    if let TimeOptions::Absolute { time_format } = &directory_container_settings
+        .metadata_column_settings
+        .time_options
+    {
+        if StrftimeItems::new(time_format).any(|item| item == Item::Error) {
+            return Err((
+                "path_container_settings.directory_container_settings.metadata_column_settings.time_options",
+                format!("{:?} is not a valid time format", time_format),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn validate_settings_default_settings() {
+    assert!(validate_settings(&Settings::default()).is_ok());
+}
+
+#[test]
+fn validate_settings_empty_truncated_text_indicator() {
+    let mut settings = Settings::default();
+    settings
+        .path_container_settings
+        .directory_container_settings
+        .truncated_text_indicator = String::new();
+
+    assert!(validate_settings(&settings).is_err());
+}
+
+#[test]
+fn validate_settings_invalid_time_format() {
+    let mut settings = Settings::default();
+    settings
+        .path_container_settings
+        .directory_container_settings
+        .metadata_column_settings
+        .time_options = TimeOptions::Absolute {
+        time_format: String::from("%Y-%"),
+    };
+
+    assert!(validate_settings(&settings).is_err());
+}
+
+#[test]
+fn validate_settings_zero_width_border_symbol() {
+    let mut settings = Settings::default();
+    settings
+        .path_container_settings
+        .directory_container_settings
+        .vertical_border_symbol = '\u{200b}';
+
+    assert!(validate_settings(&settings).is_err());
+}
+
+// The sample settings file from before `sort_settings` replaced
+// `sort_directory_item_by_item_type_indicator`
+#[cfg(test)]
+const BASELINE_SETTINGS_JSON: &str = r#"{
+    "path_container_settings": {
+        "number_of_directory_containers_to_print_option": null,
+        "spaces_between_directory_containers": 1,
+        "char_between_directory_containers": " ",
+        "spaces_between_directory_container_rows": 1,
+        "char_between_directory_container_rows": " ",
+        "filler_char": " ",
+        "directory_container_settings": {
+            "sort_directory_item_by_item_type_indicator": false,
+            "should_display_hidden_files": false,
+            "truncation_options": {
+                "Outliers": {
+                    "should_include_truncated_text_indicator_in_length": true
+                }
+            },
+            "truncated_text_indicator": "...",
+            "horizontal_border_symbol": "-",
+            "vertical_border_symbol": "|",
+            "content_divider_symbol": "=",
+            "padding_symbol_to_center_directory_names": " ",
+            "directory_item_settings": {
+                "item_type_indicator_directory": "(D)",
+                "item_type_indicator_file": "(F)",
+                "item_type_indicator_symlink": "(S)",
+                "item_type_indicator_unknown": "(U)"
+            }
+        }
+    }
+}"#;
+
+#[test]
+fn get_settings_baseline_settings_file() {
+    let settings_path = env::temp_dir().join(format!(
+        "termifind-baseline-settings-{}.json",
+        std::process::id()
+    ));
+
+    for (should_sort_by_item_type_indicator, sort_key_vec) in [
+        (false, vec![SortKey::Name]),
+        (true, vec![SortKey::ItemType, SortKey::Name]),
+    ]
+    .iter()
+    {
+        std::fs::write(
+            &settings_path,
+            BASELINE_SETTINGS_JSON.replace(
+                "\"sort_directory_item_by_item_type_indicator\": false",
+                &format!(
+                    "\"sort_directory_item_by_item_type_indicator\": {}",
+                    should_sort_by_item_type_indicator
+                ),
+            ),
+        )
+        .unwrap();
+
+        let settings_result = get_settings(std::slice::from_ref(&settings_path));
+        let _ = std::fs::remove_file(&settings_path);
+
+        let settings =
+            settings_result.unwrap_or_else(|settings_error| panic!("{}", settings_error));
+        assert_eq!(
+            &settings
+                .path_container_settings
+                .directory_container_settings
+                .sort_settings
+                .sort_key_vec,
+            sort_key_vec
+        );
+    }
+}
+
+#[test]
+fn get_settings_invalid_settings_file() {
+    let settings_path = env::temp_dir().join(format!(
+        "termifind-invalid-settings-{}.json",
+        std::process::id()
+    ));
+    std::fs::write(
+        &settings_path,
+        r#"{
+    "path_container_settings": {
+        "filler_char": " ",
+        "directory_container_settings": {
+            "truncated_text_indicator": ""
+        }
+    }
+}"#,
+    )
+    .unwrap();
+
+    let settings_result = get_settings(std::slice::from_ref(&settings_path));
+    let _ = std::fs::remove_file(&settings_path);
+
+    match settings_result {
+        Err(settings_error) => assert_eq!(
+            settings_error.to_string(),
+            format!(
+                "{}:5:42: 'path_container_settings.directory_container_settings.truncated_text_indicator': must not be empty",
+                settings_path.display()
+            )
+        ),
+        Ok(_) => panic!("the empty truncated text indicator was accepted"),
+    }
+}
+
+#[test]
+fn merge_settings_values_partial_settings() {
+    let mut settings_value = serde_json::to_value(Settings::default()).unwrap();
+    merge_settings_values(
+        &mut settings_value,
+        serde_json::json!({
+            "path_container_settings": {
+                "directory_container_settings": { "vertical_border_symbol": "#" }
+            }
+        }),
+    );
+    let settings: Settings = serde_json::from_value(settings_value).unwrap();
+    let directory_container_settings = settings
+        .path_container_settings
+        .directory_container_settings;
+
+    assert_eq!(directory_container_settings.vertical_border_symbol, '#');
+    assert_eq!(directory_container_settings.horizontal_border_symbol, '-');
+}
+
+#[test]
+fn merge_settings_values_different_truncation_options_variant() {
+    let mut settings_value = serde_json::to_value(Settings::default()).unwrap();
+    merge_settings_values(
+        &mut settings_value,
+        serde_json::json!({
+            "path_container_settings": {
+                "directory_container_settings": {
+                    "truncation_options": {
+                        "Constant": {
+                            "constant": 5,
+                            "should_include_truncated_text_indicator_in_length": false
+                        }
+                    }
+                }
+            }
+        }),
+    );
+    let settings: Settings = serde_json::from_value(settings_value).unwrap();
+
+    assert!(matches!(
+        settings
+            .path_container_settings
+            .directory_container_settings
+            .truncation_options,
+        TruncationOptions::Constant { constant: 5, .. }
+    ));
+}
+
+#[test]
+fn truncation_options_get_next_cycles_back() {
+    let truncation_options = TruncationOptions::Outliers {
+        should_include_truncated_text_indicator_in_length: false,
+    };
+    let mut next_truncation_options = truncation_options.get_next();
+    let mut name_vec = vec![truncation_options.get_name()];
+
+    while next_truncation_options.get_name() != truncation_options.get_name() {
+        name_vec.push(next_truncation_options.get_name());
+        next_truncation_options = next_truncation_options.get_next();
+    }
+
+    assert_eq!(
+        name_vec,
+        vec![
+            "Outliers",
+            "AverageFileNameLength",
+            "Level 1",
+            "HorizontalFit",
+            "NoTruncation"
+        ]
+    );
+    assert!(matches!(
+        TruncationOptions::AverageFileNameLength {
+            should_include_truncated_text_indicator_in_length: false
+        }
+        .get_next(),
+        TruncationOptions::Level {
+            level: 1,
+            should_include_truncated_text_indicator_in_length: false
+        }
+    ));
 }
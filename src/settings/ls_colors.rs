@@ -0,0 +1,194 @@
+use std::{collections::HashMap, env};
+
+use crate::utils::string::styling::{Color, Style};
+
+const LS_COLORS_ENVIRONMENT_VARIABLE: &str = "LS_COLORS";
+
+/// The styles from an `LS_COLORS` value, as produced by `dircolors`.  Only the parts that have a
+/// matching style in termifind are kept; anything else is ignored.
+#[derive(Clone, Default)]
+pub struct LsColors {
+    file_type_style_map: HashMap<String, Style>,
+    file_name_suffix_style_vec: Vec<(String, Style)>,
+    should_style_symlinks_as_target: bool,
+}
+
+impl LsColors {
+    /// Returns `None` when `LS_COLORS` is unset or empty
+    pub fn from_environment() -> Option<Self> {
+        env::var(LS_COLORS_ENVIRONMENT_VARIABLE)
+            .ok()
+            .filter(|ls_colors| !ls_colors.is_empty())
+            .map(|ls_colors| LsColors::parse(&ls_colors))
+    }
+
+    fn parse(ls_colors: &str) -> Self {
+        let mut parsed_ls_colors = LsColors::default();
+
+        for entry in ls_colors.split(':') {
+            let (key, sgr_parameters) = match entry.find('=') {
+                Some(index) => (&entry[..index], &entry[index + 1..]),
+                None => continue,
+            };
+
+            if let Some(file_name_suffix) = key.strip_prefix('*') {
+                parsed_ls_colors.file_name_suffix_style_vec.push((
+                    String::from(file_name_suffix),
+                    LsColors::get_style_from_sgr_parameters(sgr_parameters),
+                ));
+            } else if key == "ln" && sgr_parameters == "target" {
+                parsed_ls_colors.should_style_symlinks_as_target = true;
+            } else {
+                parsed_ls_colors.file_type_style_map.insert(
+                    String::from(key),
+                    LsColors::get_style_from_sgr_parameters(sgr_parameters),
+                );
+            }
+        }
+
+        parsed_ls_colors
+    }
+
+    /// Converts SGR parameters such as `01;38;5;208` into a style, skipping the ones a style can't
+    /// represent (such as blinking)
+    fn get_style_from_sgr_parameters(sgr_parameters: &str) -> Style {
+        let mut style = Style::default();
+        let mut sgr_parameter_iterator = sgr_parameters
+            .split(';')
+            .map(|sgr_parameter| sgr_parameter.parse::<u8>().ok());
+
+        while let Some(sgr_parameter_option) = sgr_parameter_iterator.next() {
+            let sgr_parameter = match sgr_parameter_option {
+                Some(sgr_parameter) => sgr_parameter,
+                None => continue,
+            };
+
+            match sgr_parameter {
+                0 => style = Style::default(),
+                1 => style.is_bold = true,
+                2 => style.is_dimmed = true,
+                3 => style.is_italic = true,
+                4 => style.is_underlined = true,
+                7 => style.is_reversed = true,
+                30..=37 => {
+                    style.foreground_color_option = Some(get_basic_color(sgr_parameter - 30))
+                }
+                40..=47 => {
+                    style.background_color_option = Some(get_basic_color(sgr_parameter - 40))
+                }
+                90..=97 => {
+                    style.foreground_color_option = Some(get_bright_color(sgr_parameter - 90))
+                }
+                100..=107 => {
+                    style.background_color_option = Some(get_bright_color(sgr_parameter - 100))
+                }
+                38 | 48 => {
+                    let color_option = match sgr_parameter_iterator.next().flatten() {
+                        Some(5) => sgr_parameter_iterator.next().flatten().map(Color::Color256),
+                        Some(2) => {
+                            let mut next_channel = || sgr_parameter_iterator.next().flatten();
+                            match (next_channel(), next_channel(), next_channel()) {
+                                (Some(red), Some(green), Some(blue)) => {
+                                    Some(Color::Rgb(red, green, blue))
+                                }
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    };
+
+                    if sgr_parameter == 38 {
+                        style.foreground_color_option = color_option;
+                    } else {
+                        style.background_color_option = color_option;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        style
+    }
+
+    /// Looks up a file type key, such as `di` or `ex`
+    pub fn get_file_type_style(&self, file_type_key: &str) -> Option<&Style> {
+        self.file_type_style_map.get(file_type_key)
+    }
+
+    /// Finds the style of the longest `*.ext` pattern that `file_name` ends with
+    pub fn get_file_name_style(&self, file_name: &str) -> Option<&Style> {
+        self.file_name_suffix_style_vec
+            .iter()
+            .filter(|(file_name_suffix, _)| file_name.ends_with(file_name_suffix.as_str()))
+            .max_by_key(|(file_name_suffix, _)| file_name_suffix.len())
+            .map(|(_, style)| style)
+    }
+
+    /// `ln=target` styles symlinks like the file they point to
+    pub fn should_style_symlinks_as_target(&self) -> bool {
+        self.should_style_symlinks_as_target
+    }
+}
+
+fn get_basic_color(index: u8) -> Color {
+    match index {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        _ => Color::White,
+    }
+}
+
+fn get_bright_color(index: u8) -> Color {
+    match index {
+        0 => Color::BrightBlack,
+        1 => Color::BrightRed,
+        2 => Color::BrightGreen,
+        3 => Color::BrightYellow,
+        4 => Color::BrightBlue,
+        5 => Color::BrightMagenta,
+        6 => Color::BrightCyan,
+        _ => Color::BrightWhite,
+    }
+}
+
+#[test]
+fn parse_file_types_and_suffixes() {
+    let ls_colors = LsColors::parse("rs=0:di=01;34:ex=01;32:*.tar=01;31:*.tar.gz=04;33:bogus");
+
+    let directory_style = ls_colors.get_file_type_style("di").unwrap();
+    assert!(directory_style.is_bold);
+    assert_eq!(directory_style.foreground_color_option, Some(Color::Blue));
+    assert!(ls_colors.get_file_type_style("pi").is_none());
+
+    let archive_style = ls_colors.get_file_name_style("backup.tar.gz").unwrap();
+    assert!(archive_style.is_underlined);
+    assert_eq!(archive_style.foreground_color_option, Some(Color::Yellow));
+    assert_eq!(
+        ls_colors
+            .get_file_name_style("backup.tar")
+            .unwrap()
+            .foreground_color_option,
+        Some(Color::Red)
+    );
+    assert!(ls_colors.get_file_name_style("tar").is_none());
+    assert!(!ls_colors.should_style_symlinks_as_target());
+    assert!(LsColors::parse("ln=target").should_style_symlinks_as_target());
+}
+
+#[test]
+fn get_style_from_sgr_parameters_extended_colors() {
+    let style = LsColors::get_style_from_sgr_parameters("38;5;208;48;2;0;95;135;5;101");
+
+    assert_eq!(style.foreground_color_option, Some(Color::Color256(208)));
+    assert_eq!(style.background_color_option, Some(Color::BrightRed));
+    assert!(!style.is_bold);
+
+    let reset_style = LsColors::get_style_from_sgr_parameters("01;31;0");
+    assert!(reset_style.foreground_color_option.is_none());
+    assert!(!reset_style.is_bold);
+}
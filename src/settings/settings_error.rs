@@ -0,0 +1,95 @@
+use std::{error, fmt, io, path::PathBuf};
+
+#[derive(Debug)]
+pub enum SettingsError {
+    Read {
+        settings_path: PathBuf,
+        io_error: io::Error,
+    },
+    Parse {
+        settings_path: PathBuf,
+        line: usize,
+        column: usize,
+        key_path_option: Option<String>,
+        message: String,
+    },
+    Invalid {
+        settings_path: PathBuf,
+        position_option: Option<(usize, usize)>,
+        key_path: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SettingsError::Read {
+                settings_path,
+                io_error,
+            } => write!(
+                formatter,
+                "could not read settings file '{}': {}",
+                settings_path.display(),
+                io_error
+            ),
+            SettingsError::Parse {
+                settings_path,
+                line,
+                column,
+                key_path_option,
+                message,
+            } => match key_path_option {
+                Some(key_path) => write!(
+                    formatter,
+                    "{}:{}:{}: '{}': {}",
+                    settings_path.display(),
+                    line,
+                    column,
+                    key_path,
+                    message
+                ),
+                None => write!(
+                    formatter,
+                    "{}:{}:{}: {}",
+                    settings_path.display(),
+                    line,
+                    column,
+                    message
+                ),
+            },
+            SettingsError::Invalid {
+                settings_path,
+                position_option,
+                key_path,
+                message,
+            } => match position_option {
+                Some((line, column)) => write!(
+                    formatter,
+                    "{}:{}:{}: '{}': {}",
+                    settings_path.display(),
+                    line,
+                    column,
+                    key_path,
+                    message
+                ),
+                None => write!(
+                    formatter,
+                    "{}: '{}': {}",
+                    settings_path.display(),
+                    key_path,
+                    message
+                ),
+            },
+        }
+    }
+}
+
+impl error::Error for SettingsError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SettingsError::Read { io_error, .. } => Some(io_error),
+            _ => None,
+        }
+    }
+}
@@ -1,2 +1,4 @@
 pub mod formatting;
+pub mod sanitizing;
+pub mod sorting;
 pub mod styling;
@@ -1,7 +1,60 @@
+use std::time::Duration;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::settings::SizeOptions;
+
+/// Returns the number of terminal columns a single grapheme cluster occupies.  Sequences like
+/// emoji joined with zero-width joiners are measured as the sum of their parts, but terminals
+/// render them as one wide glyph, so the width is capped at two columns.
+fn get_grapheme_display_width(grapheme: &str) -> usize {
+    std::cmp::min(grapheme.width(), 2)
+}
+
+pub fn get_display_width(text: &str) -> usize {
+    text.graphemes(true).map(get_grapheme_display_width).sum()
+}
+
+#[test]
+fn get_display_width_ascii() {
+    assert_eq!(get_display_width("resume_final.pdf"), 16);
+}
+
+#[test]
+fn get_display_width_combining_marks() {
+    assert_eq!(get_display_width("re\u{301}sume\u{301}_final.pdf"), 16);
+}
+
+#[test]
+fn get_display_width_cjk() {
+    assert_eq!(get_display_width("日本語.txt"), 10);
+}
+
+#[test]
+fn get_display_width_emoji() {
+    assert_eq!(get_display_width("🦀.rs"), 5);
+    assert_eq!(get_display_width("👨\u{200d}👩\u{200d}👧.png"), 6);
+}
+
+/// Returns the longest prefix of `text`, made of whole grapheme clusters, that fits in `width`
+/// terminal columns.
+pub fn take_display_width(text: &str, width: usize) -> &str {
+    let mut current_width = 0;
+
+    for (index, grapheme) in text.grapheme_indices(true) {
+        current_width += get_grapheme_display_width(grapheme);
+
+        if current_width > width {
+            return &text[..index];
+        }
+    }
+
+    text
+}
+
 pub fn make_repeated_char_string(character: char, repetitions: usize) -> String {
-    std::iter::repeat(character)
-        .take(repetitions)
-        .collect::<String>()
+    std::iter::repeat_n(character, repetitions).collect::<String>()
 }
 
 #[test]
@@ -14,7 +67,7 @@ pub fn add_padding_to_center_string(
     padding_symbol: char,
     required_length: usize,
 ) -> String {
-    let length_of_text = text.chars().count();
+    let length_of_text = get_display_width(text);
     let left_padding_length = (required_length - length_of_text) / 2;
     let mut right_padding_length = left_padding_length;
 
@@ -67,24 +120,29 @@ pub fn truncate_text(
     mut text_length_after_truncation: usize,
     truncated_text_indicator_option: Option<(String, bool)>,
 ) -> String {
-    if text.chars().count() > text_length_after_truncation {
+    if get_display_width(&text) > text_length_after_truncation {
         return match truncated_text_indicator_option {
             Some(text_to_append) => {
                 if text_to_append.1 {
-                    if text_length_after_truncation <= text_to_append.0.chars().count() {
-                        return String::from(&text_to_append.0[..(text_length_after_truncation)]);
+                    let length_of_text_to_append = get_display_width(&text_to_append.0);
+
+                    if text_length_after_truncation <= length_of_text_to_append {
+                        return String::from(take_display_width(
+                            &text_to_append.0,
+                            text_length_after_truncation,
+                        ));
                     } else {
-                        text_length_after_truncation -= text_to_append.0.chars().count()
+                        text_length_after_truncation -= length_of_text_to_append
                     }
                 }
 
                 format!(
                     "{}{}",
-                    String::from(&text[..(text_length_after_truncation)]),
+                    take_display_width(&text, text_length_after_truncation),
                     text_to_append.0
                 )
             }
-            None => String::from(&text[..(text_length_after_truncation)]),
+            None => String::from(take_display_width(&text, text_length_after_truncation)),
         };
     }
 
@@ -210,3 +268,151 @@ fn truncate_text_longer_file_name_with_text_to_append_option_include_appended_te
         String::from("")
     )
 }
+
+#[test]
+fn truncate_text_combining_marks() {
+    assert_eq!(
+        truncate_text(
+            String::from("re\u{301}sume\u{301}_final.pdf"),
+            8,
+            Some((String::from("..."), true)),
+        ),
+        String::from("re\u{301}sum...")
+    )
+}
+
+#[test]
+fn truncate_text_combining_mark_at_boundary() {
+    assert_eq!(
+        truncate_text(String::from("re\u{301}sume\u{301}_final.pdf"), 2, None),
+        String::from("re\u{301}")
+    )
+}
+
+#[test]
+fn truncate_text_cjk() {
+    assert_eq!(
+        truncate_text(
+            String::from("日本語のファイル.txt"),
+            7,
+            Some((String::from("..."), false)),
+        ),
+        String::from("日本語...")
+    )
+}
+
+#[test]
+fn truncate_text_cjk_does_not_split_wide_character() {
+    assert_eq!(
+        truncate_text(String::from("日本語のファイル.txt"), 5, None),
+        String::from("日本")
+    )
+}
+
+#[test]
+fn truncate_text_emoji() {
+    assert_eq!(
+        truncate_text(
+            String::from("👨\u{200d}👩\u{200d}👧 family photo.png"),
+            6,
+            Some((String::from("..."), true)),
+        ),
+        String::from("👨\u{200d}👩\u{200d}👧 ...")
+    )
+}
+
+#[test]
+fn add_padding_to_center_string_cjk() {
+    assert_eq!(
+        String::from("  日本  "),
+        add_padding_to_center_string("日本", ' ', 8)
+    );
+}
+
+/// Formats a size in bytes like `ls -h`: one decimal place below 10 units and none above
+pub fn format_size(size: u64, size_options: &SizeOptions) -> String {
+    let (base, unit_prefixes) = match size_options {
+        SizeOptions::Bytes => return size.to_string(),
+        SizeOptions::Iec => (1024.0, ["Ki", "Mi", "Gi", "Ti", "Pi", "Ei"]),
+        SizeOptions::Si => (1000.0, ["k", "M", "G", "T", "P", "E"]),
+    };
+
+    let mut value = size as f64;
+    let mut unit_prefix_option = None;
+
+    for unit_prefix in unit_prefixes.iter() {
+        if value < base {
+            break;
+        }
+
+        value /= base;
+        unit_prefix_option = Some(unit_prefix);
+    }
+
+    match unit_prefix_option {
+        None => size.to_string(),
+        Some(unit_prefix) if value < 10.0 => format!("{:.1}{}", value, unit_prefix),
+        Some(unit_prefix) => format!("{:.0}{}", value, unit_prefix),
+    }
+}
+
+#[test]
+fn format_size_units() {
+    assert_eq!(format_size(1536, &SizeOptions::Bytes), "1536");
+    assert_eq!(format_size(512, &SizeOptions::Iec), "512");
+    assert_eq!(format_size(1536, &SizeOptions::Iec), "1.5Ki");
+    assert_eq!(format_size(1536, &SizeOptions::Si), "1.5k");
+    assert_eq!(format_size(25 * 1024 * 1024, &SizeOptions::Iec), "25Mi");
+    assert_eq!(format_size(u64::MAX, &SizeOptions::Si), "18E");
+}
+
+/// Formats the time elapsed since something happened in its largest whole unit, such as
+/// `5 minutes ago`
+pub fn format_relative_time(elapsed_time: Duration) -> String {
+    let units = [
+        ("year", 365 * 24 * 60 * 60),
+        ("month", 30 * 24 * 60 * 60),
+        ("day", 24 * 60 * 60),
+        ("hour", 60 * 60),
+        ("minute", 60),
+        ("second", 1),
+    ];
+    let elapsed_seconds = elapsed_time.as_secs();
+
+    for (unit_name, unit_seconds) in units.iter() {
+        let number_of_units = elapsed_seconds / unit_seconds;
+
+        if number_of_units > 0 {
+            return format!(
+                "{} {}{} ago",
+                number_of_units,
+                unit_name,
+                if number_of_units == 1 { "" } else { "s" }
+            );
+        }
+    }
+
+    String::from("just now")
+}
+
+#[test]
+fn format_relative_time_units() {
+    assert_eq!(format_relative_time(Duration::from_millis(300)), "just now");
+    assert_eq!(format_relative_time(Duration::from_secs(1)), "1 second ago");
+    assert_eq!(
+        format_relative_time(Duration::from_secs(59)),
+        "59 seconds ago"
+    );
+    assert_eq!(
+        format_relative_time(Duration::from_secs(90)),
+        "1 minute ago"
+    );
+    assert_eq!(
+        format_relative_time(Duration::from_secs(3 * 24 * 60 * 60)),
+        "3 days ago"
+    );
+    assert_eq!(
+        format_relative_time(Duration::from_secs(400 * 24 * 60 * 60)),
+        "1 year ago"
+    );
+}
@@ -0,0 +1,81 @@
+use crate::settings::ControlCharacterOptions;
+
+/// Characters that reorder the text around them, which can make a name display as something other
+/// than what it is
+fn is_bidirectional_formatting_character(character: char) -> bool {
+    matches!(character, '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+pub fn escape_control_characters(
+    text: &str,
+    control_character_options: &ControlCharacterOptions,
+) -> String {
+    let mut escaped_text = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        if !character.is_control() && !is_bidirectional_formatting_character(character) {
+            escaped_text.push(character);
+            continue;
+        }
+
+        let code_point = character as u32;
+
+        match control_character_options {
+            ControlCharacterOptions::Caret => match code_point {
+                0x00..=0x1f => {
+                    escaped_text.push('^');
+                    escaped_text.push((code_point as u8 ^ 0x40) as char);
+                }
+                0x7f => escaped_text.push_str("^?"),
+                _ => escaped_text.push_str(&format!("\\u{{{:x}}}", code_point)),
+            },
+            ControlCharacterOptions::Hexadecimal => match code_point {
+                0x00..=0xff => escaped_text.push_str(&format!("\\x{:02x}", code_point)),
+                _ => escaped_text.push_str(&format!("\\u{{{:x}}}", code_point)),
+            },
+            ControlCharacterOptions::QuestionMark => escaped_text.push('?'),
+        }
+    }
+
+    escaped_text
+}
+
+#[test]
+fn escape_control_characters_no_control_characters() {
+    assert_eq!(
+        escape_control_characters("résumé.pdf", &ControlCharacterOptions::Caret),
+        String::from("résumé.pdf")
+    );
+}
+
+#[test]
+fn escape_control_characters_caret() {
+    assert_eq!(
+        escape_control_characters("a\x1b[31mb\nc\x7f", &ControlCharacterOptions::Caret),
+        String::from("a^[[31mb^Jc^?")
+    );
+}
+
+#[test]
+fn escape_control_characters_hexadecimal() {
+    assert_eq!(
+        escape_control_characters("a\x1b[31mb\nc\u{9b}", &ControlCharacterOptions::Hexadecimal),
+        String::from("a\\x1b[31mb\\x0ac\\x9b")
+    );
+}
+
+#[test]
+fn escape_control_characters_question_mark() {
+    assert_eq!(
+        escape_control_characters("a\x1b[31mb\nc", &ControlCharacterOptions::QuestionMark),
+        String::from("a?[31mb?c")
+    );
+}
+
+#[test]
+fn escape_control_characters_bidirectional_formatting_character() {
+    assert_eq!(
+        escape_control_characters("invoice\u{202e}fdp.exe", &ControlCharacterOptions::Caret),
+        String::from("invoice\\u{202e}fdp.exe")
+    );
+}
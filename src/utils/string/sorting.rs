@@ -0,0 +1,70 @@
+use std::{cmp::Ordering, iter::Peekable, str::Chars};
+
+fn take_digits(chars: &mut Peekable<Chars>) -> String {
+    let mut digits = String::new();
+
+    while let Some(character) = chars.next_if(char::is_ascii_digit) {
+        digits.push(character);
+    }
+
+    digits
+}
+
+/// Compares like `ls -v`: runs of digits compare by their value, so `file2` comes before `file10`,
+/// and everything else compares character by character
+pub fn compare_naturally(text: &str, other_text: &str) -> Ordering {
+    let mut chars = text.chars().peekable();
+    let mut other_chars = other_text.chars().peekable();
+
+    loop {
+        let ordering = match (chars.peek(), other_chars.peek()) {
+            (None, None) => break,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(character), Some(other_character))
+                if character.is_ascii_digit() && other_character.is_ascii_digit() =>
+            {
+                let digits = take_digits(&mut chars);
+                let other_digits = take_digits(&mut other_chars);
+                let value = digits.trim_start_matches('0');
+                let other_value = other_digits.trim_start_matches('0');
+
+                // Equal values are told apart by their leading zeros at the end
+                value
+                    .len()
+                    .cmp(&other_value.len())
+                    .then_with(|| value.cmp(other_value))
+            }
+            (Some(&character), Some(&other_character)) => {
+                chars.next();
+                other_chars.next();
+                character.cmp(&other_character)
+            }
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    text.cmp(other_text)
+}
+
+#[test]
+fn compare_naturally_digit_runs() {
+    let mut text_vec = vec!["file10.txt", "file2.txt", "file1.txt", "file02.txt", "file"];
+    text_vec.sort_by(|a, b| compare_naturally(a, b));
+
+    assert_eq!(
+        text_vec,
+        vec!["file", "file1.txt", "file02.txt", "file2.txt", "file10.txt"]
+    );
+}
+
+#[test]
+fn compare_naturally_versions() {
+    assert_eq!(compare_naturally("v1.9.0", "v1.10.0"), Ordering::Less);
+    assert_eq!(compare_naturally("v2.0", "v10.0"), Ordering::Less);
+    assert_eq!(compare_naturally("B", "a"), Ordering::Less);
+    assert_eq!(compare_naturally("same1", "same1"), Ordering::Equal);
+}
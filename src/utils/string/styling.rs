@@ -1,28 +1,242 @@
-use console::style;
+use std::convert::TryFrom;
 
-#[allow(dead_code)]
+/// A color given by name (`"red"`, `"bright_red"`), by 256-color palette index (`"208"` or `208`),
+/// or as truecolor (`"#ff8700"`)
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone, Debug, PartialEq)]
+#[serde(try_from = "ColorValue", into = "String")]
 pub enum Color {
     Black,
-    Blue,
-    Cyan,
+    Red,
     Green,
+    Yellow,
+    Blue,
     Magenta,
-    Red,
+    Cyan,
     White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    Color256(u8),
+    Rgb(u8, u8, u8),
+}
+
+const COLOR_NAMES: [(&str, Color); 16] = [
+    ("black", Color::Black),
+    ("red", Color::Red),
+    ("green", Color::Green),
+    ("yellow", Color::Yellow),
+    ("blue", Color::Blue),
+    ("magenta", Color::Magenta),
+    ("cyan", Color::Cyan),
+    ("white", Color::White),
+    ("bright_black", Color::BrightBlack),
+    ("bright_red", Color::BrightRed),
+    ("bright_green", Color::BrightGreen),
+    ("bright_yellow", Color::BrightYellow),
+    ("bright_blue", Color::BrightBlue),
+    ("bright_magenta", Color::BrightMagenta),
+    ("bright_cyan", Color::BrightCyan),
+    ("bright_white", Color::BrightWhite),
+];
+
+/// What a color may be written as in the settings file
+#[derive(serde_derive::Deserialize)]
+#[serde(untagged)]
+enum ColorValue {
+    Index(u8),
+    Text(String),
+}
+
+impl TryFrom<ColorValue> for Color {
+    type Error = String;
+
+    fn try_from(color_value: ColorValue) -> Result<Self, Self::Error> {
+        let color_text = match color_value {
+            ColorValue::Index(index) => return Ok(Color::Color256(index)),
+            ColorValue::Text(color_text) => color_text,
+        };
+
+        if let Some((_, color)) = COLOR_NAMES.iter().find(|(name, _)| *name == color_text) {
+            return Ok(color.clone());
+        }
+
+        if let Ok(index) = color_text.parse::<u8>() {
+            return Ok(Color::Color256(index));
+        }
+
+        if let Some(hexadecimal_digits) = color_text.strip_prefix('#') {
+            if hexadecimal_digits.len() == 6 && hexadecimal_digits.is_ascii() {
+                let channel_results: Vec<Result<u8, _>> = (0..3)
+                    .map(|index| u8::from_str_radix(&hexadecimal_digits[index * 2..][..2], 16))
+                    .collect();
+
+                if let [Ok(red), Ok(green), Ok(blue)] = channel_results[..] {
+                    return Ok(Color::Rgb(red, green, blue));
+                }
+            }
+        }
+
+        Err(format!(
+            "invalid color '{}', expected a color name, a number from 0 to 255, or '#rrggbb'",
+            color_text
+        ))
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Color256(index) => index.to_string(),
+            Color::Rgb(red, green, blue) => format!("#{:02x}{:02x}{:02x}", red, green, blue),
+            color => String::from(
+                COLOR_NAMES
+                    .iter()
+                    .find(|(_, named_color)| *named_color == color)
+                    .map(|(name, _)| *name)
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+impl Color {
+    /// Returns the SGR parameters that set this color, offset by 10 for a background color
+    fn get_sgr_parameters(&self, is_background: bool) -> String {
+        let offset = if is_background { 10 } else { 0 };
+
+        match self {
+            Color::Color256(index) => format!("{};5;{}", 38 + offset, index),
+            Color::Rgb(red, green, blue) => {
+                format!("{};2;{};{};{}", 38 + offset, red, green, blue)
+            }
+            color => {
+                let index = COLOR_NAMES
+                    .iter()
+                    .position(|(_, named_color)| named_color == color)
+                    .unwrap_or_default();
+
+                match index {
+                    0..=7 => (30 + offset + index).to_string(),
+                    _ => (90 + offset + index - 8).to_string(),
+                }
+            }
+        }
+    }
+}
+
+#[derive(serde_derive::Deserialize, serde_derive::Serialize, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Style {
+    pub foreground_color_option: Option<Color>,
+    pub background_color_option: Option<Color>,
+    pub is_bold: bool,
+    pub is_dimmed: bool,
+    pub is_italic: bool,
+    pub is_underlined: bool,
+    pub is_reversed: bool,
+}
+
+impl Style {
+    pub fn with_foreground_color(color: Color) -> Self {
+        Style {
+            foreground_color_option: Some(color),
+            ..Style::default()
+        }
+    }
+
+    fn get_sgr_parameters(&self) -> Vec<String> {
+        let mut sgr_parameters: Vec<String> = Vec::new();
+
+        for (is_enabled, sgr_parameter) in [
+            (self.is_bold, "1"),
+            (self.is_dimmed, "2"),
+            (self.is_italic, "3"),
+            (self.is_underlined, "4"),
+            (self.is_reversed, "7"),
+        ]
+        .iter()
+        {
+            if *is_enabled {
+                sgr_parameters.push(String::from(*sgr_parameter));
+            }
+        }
+
+        if let Some(foreground_color) = &self.foreground_color_option {
+            sgr_parameters.push(foreground_color.get_sgr_parameters(false));
+        }
+
+        if let Some(background_color) = &self.background_color_option {
+            sgr_parameters.push(background_color.get_sgr_parameters(true));
+        }
+
+        sgr_parameters
+    }
+}
+
+/// Wraps `text` in the escape sequences for `style`
+pub fn get_styled_text(text: &str, style: &Style) -> String {
+    let sgr_parameters = style.get_sgr_parameters();
+
+    if sgr_parameters.is_empty() {
+        return String::from(text);
+    }
+
+    format!("\x1b[{}m{}\x1b[0m", sgr_parameters.join(";"), text)
 }
 
-pub fn print_colored_text(text: String, color: Color) {
-    let styled_text = style(text);
+#[test]
+fn color_from_settings_values() {
+    let parse_color = |json: &str| serde_json::from_str::<Color>(json);
+
+    assert_eq!(parse_color("\"bright_red\"").unwrap(), Color::BrightRed);
+    assert_eq!(parse_color("208").unwrap(), Color::Color256(208));
+    assert_eq!(parse_color("\"208\"").unwrap(), Color::Color256(208));
+    assert_eq!(parse_color("\"#FF8700\"").unwrap(), Color::Rgb(255, 135, 0));
+    assert!(parse_color("\"orange\"").is_err());
+    assert!(parse_color("256").is_err());
+    assert!(parse_color("\"#ff87\"").is_err());
+    assert!(parse_color("\"#ff87é\"").is_err());
+}
+
+#[test]
+fn color_round_trips_through_settings_values() {
+    for color in [
+        Color::Magenta,
+        Color::BrightWhite,
+        Color::Color256(17),
+        Color::Rgb(1, 2, 3),
+    ]
+    .iter()
+    {
+        let color_value = serde_json::to_value(color).unwrap();
+        assert_eq!(
+            &serde_json::from_value::<Color>(color_value).unwrap(),
+            color
+        );
+    }
+}
 
-    let styled_colored_text = match color {
-        Color::Black => styled_text.black(),
-        Color::Blue => styled_text.blue(),
-        Color::Cyan => styled_text.cyan(),
-        Color::Green => styled_text.green(),
-        Color::Magenta => styled_text.magenta(),
-        Color::Red => styled_text.red(),
-        Color::White => styled_text.white(),
+#[test]
+fn style_sgr_parameters() {
+    let style = Style {
+        foreground_color_option: Some(Color::BrightBlue),
+        background_color_option: Some(Color::Rgb(0, 95, 135)),
+        is_bold: true,
+        is_reversed: true,
+        ..Style::default()
     };
 
-    print!("{}", styled_colored_text);
+    assert_eq!(style.get_sgr_parameters().join(";"), "1;7;94;48;2;0;95;135");
+    assert_eq!(
+        Style::with_foreground_color(Color::Color256(208))
+            .get_sgr_parameters()
+            .join(";"),
+        "38;5;208"
+    );
+    assert!(Style::default().get_sgr_parameters().is_empty());
 }
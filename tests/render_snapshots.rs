@@ -0,0 +1,878 @@
+//! Renders temporary directory fixtures with the `termifind` binary and compares the output
+//! against the snapshots in `tests/snapshots`.  When stderr isn't a terminal, termifind prints
+//! the path once and exits, so the output only depends on the fixture, the settings and
+//! `--width`.
+//!
+//! The library's `PathContainer::render_to_string` is checked against the same snapshots.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to write the current output as the new snapshots.
+
+use std::{
+    env,
+    fs::{create_dir_all, read_to_string, remove_dir_all, write, File},
+    path::{Path, PathBuf},
+    process::{self, Command},
+    time::{Duration, UNIX_EPOCH},
+};
+
+use termifind::{settings::SortKey, PathContainer, Settings};
+
+const FIXTURE_DIRECTORY_NAME: &str = "fixture";
+
+struct Fixture {
+    temporary_directory_path: PathBuf,
+}
+
+impl Fixture {
+    /// Creates `fixture/` inside a new temporary directory, with an entry for each path; paths
+    /// ending in `/` are created as directories and the rest as empty files
+    fn new(test_name: &str, entry_paths: &[&str]) -> Self {
+        let temporary_directory_path =
+            env::temp_dir().join(format!("termifind-{}-{}", test_name, process::id()));
+        let _ = remove_dir_all(&temporary_directory_path);
+
+        let fixture = Fixture {
+            temporary_directory_path,
+        };
+        create_dir_all(fixture.get_path("")).unwrap();
+
+        for entry_path in entry_paths {
+            if entry_path.ends_with('/') {
+                create_dir_all(fixture.get_path(entry_path)).unwrap();
+            } else {
+                let path = fixture.get_path(entry_path);
+                create_dir_all(path.parent().unwrap()).unwrap();
+                File::create(path).unwrap();
+            }
+        }
+
+        fixture
+    }
+
+    /// `XDG_CONFIG_HOME` when rendering, outside of `fixture/`
+    fn get_user_configuration_path(&self) -> PathBuf {
+        self.temporary_directory_path.join("config")
+    }
+
+    fn get_path(&self, relative_path: &str) -> PathBuf {
+        self.temporary_directory_path
+            .join(FIXTURE_DIRECTORY_NAME)
+            .join(relative_path)
+    }
+
+    /// Renders `relative_path` inside the fixture.  Only the containers from `fixture/` down are
+    /// printed, so the output doesn't depend on where the temporary directory is.
+    fn render(&self, relative_path: &str, width: usize, settings_json: &str) -> String {
+        let output = self
+            .get_command(relative_path, width, settings_json)
+            .arg("--color=never")
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    fn get_command(&self, relative_path: &str, width: usize, settings_json: &str) -> Command {
+        let current_path = self.get_path(relative_path);
+        let number_of_directory_containers_to_print = current_path
+            .strip_prefix(self.get_path(""))
+            .unwrap()
+            .components()
+            .count()
+            + 1;
+
+        let mut settings_value: serde_json::Value = serde_json::from_str(settings_json).unwrap();
+        settings_value["path_container_settings"]
+            ["number_of_directory_containers_to_print_option"] =
+            serde_json::Value::from(number_of_directory_containers_to_print);
+
+        let settings_path = self.temporary_directory_path.join("settings.json");
+        write(&settings_path, settings_value.to_string()).unwrap();
+
+        let mut command = Command::new(env!("CARGO_BIN_EXE_termifind"));
+        command
+            .arg(format!("--width={}", width))
+            .arg(format!("--config={}", settings_path.display()))
+            .current_dir(&current_path)
+            .env("XDG_CONFIG_DIRS", &self.temporary_directory_path)
+            // Keeps the global git excludes file (and any other user configuration) out
+            .env("HOME", self.temporary_directory_path.join("home"))
+            .env("XDG_CONFIG_HOME", self.get_user_configuration_path())
+            .env_remove("LS_COLORS");
+
+        command
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.temporary_directory_path);
+    }
+}
+
+fn assert_snapshot(snapshot_name: &str, rendered_path: &str) {
+    let snapshot_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.txt", snapshot_name));
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        create_dir_all(snapshot_path.parent().unwrap()).unwrap();
+        write(&snapshot_path, rendered_path).unwrap();
+        return;
+    }
+
+    let snapshot = read_to_string(&snapshot_path).unwrap_or_else(|_| {
+        panic!(
+            "Missing snapshot '{}'; run with UPDATE_SNAPSHOTS=1 to create it",
+            snapshot_path.display()
+        )
+    });
+
+    assert!(
+        snapshot == rendered_path,
+        "'{}' does not match the snapshot\n--- expected ---\n{}\n--- rendered ---\n{}",
+        snapshot_name,
+        snapshot,
+        rendered_path
+    );
+}
+
+// Only the longest name is an outlier, well above the others
+const LONG_FILE_NAMES: [&str; 8] = [
+    "a.txt",
+    "notes.md",
+    "Cargo.toml",
+    "lib.rs",
+    "main.rs",
+    "README.md",
+    "a_rather_long_file_name_for_outliers.txt",
+    "src/",
+];
+
+fn get_truncation_settings_json(truncation_options_json: &str) -> String {
+    format!(
+        r#"{{
+            "path_container_settings": {{
+                "directory_container_settings": {{ "truncation_options": {} }}
+            }}
+        }}"#,
+        truncation_options_json
+    )
+}
+
+#[test]
+fn render_wraps_rows_that_do_not_fit() {
+    let fixture = Fixture::new(
+        "wrap",
+        &[
+            "alpha/beta/gamma/last_file.txt",
+            "alpha/beta/beta_file.txt",
+            "alpha/alpha_file.txt",
+            "root_file.txt",
+        ],
+    );
+
+    assert_snapshot("wrap_narrow", &fixture.render("alpha/beta/gamma", 40, "{}"));
+    assert_snapshot("wrap_wide", &fixture.render("alpha/beta/gamma", 120, "{}"));
+}
+
+#[test]
+fn render_truncation_options() {
+    let fixture = Fixture::new("truncation", &LONG_FILE_NAMES);
+
+    for (snapshot_name, truncation_options_json) in [
+        ("truncation_none", r#""NoTruncation""#),
+        (
+            "truncation_constant",
+            r#"{ "Constant": { "constant": 8, "should_include_truncated_text_indicator_in_length": true } }"#,
+        ),
+        (
+            "truncation_level",
+            r#"{ "Level": { "level": 1, "should_include_truncated_text_indicator_in_length": false } }"#,
+        ),
+        (
+            "truncation_average",
+            r#"{ "AverageFileNameLength": { "should_include_truncated_text_indicator_in_length": true } }"#,
+        ),
+        (
+            "truncation_outliers",
+            r#"{ "Outliers": { "should_include_truncated_text_indicator_in_length": true } }"#,
+        ),
+    ]
+    .iter()
+    {
+        assert_snapshot(
+            snapshot_name,
+            &fixture.render(
+                "",
+                120,
+                &get_truncation_settings_json(truncation_options_json),
+            ),
+        );
+    }
+}
+
+#[test]
+fn render_horizontal_fit_truncation() {
+    let fixture = Fixture::new(
+        "horizontal_fit",
+        &[
+            "a_directory_with_a_long_name/another_long_file_name.txt",
+            "a_directory_with_a_long_name/short.txt",
+            "yet_another_long_file_name.txt",
+        ],
+    );
+
+    assert_snapshot(
+        "truncation_horizontal_fit",
+        &fixture.render(
+            "a_directory_with_a_long_name",
+            50,
+            &get_truncation_settings_json(r#""HorizontalFit""#),
+        ),
+    );
+
+    // Too narrow to fit even a single character of each name, so the rows wrap instead
+    assert_snapshot(
+        "truncation_horizontal_fit_wrapped",
+        &fixture.render(
+            "a_directory_with_a_long_name",
+            20,
+            &get_truncation_settings_json(r#""HorizontalFit""#),
+        ),
+    );
+}
+
+#[test]
+fn render_hidden_files() {
+    let fixture = Fixture::new(
+        "hidden",
+        &[
+            ".hidden_file",
+            ".hidden_directory/",
+            "visible_file",
+            "visible_directory/",
+        ],
+    );
+
+    assert_snapshot("hidden_files_hidden", &fixture.render("", 80, "{}"));
+    assert_snapshot(
+        "hidden_files_displayed",
+        &fixture.render(
+            "",
+            80,
+            r#"{
+                "path_container_settings": {
+                    "directory_container_settings": { "should_display_hidden_files": true }
+                }
+            }"#,
+        ),
+    );
+}
+
+#[test]
+fn render_empty_directories() {
+    let fixture = Fixture::new("empty", &["empty/", "file.txt"]);
+
+    assert_snapshot("empty_directory", &fixture.render("empty", 80, "{}"));
+}
+
+#[test]
+fn render_every_directory_item_without_a_terminal() {
+    let file_name_vec: Vec<String> = (1..=30)
+        .map(|file_number| format!("file_{:02}.txt", file_number))
+        .collect();
+    let file_name_str_vec: Vec<&str> = file_name_vec.iter().map(String::as_str).collect();
+    let fixture = Fixture::new("no_terminal_height", &file_name_str_vec);
+
+    assert_snapshot("no_terminal_height", &fixture.render("", 60, "{}"));
+}
+
+#[test]
+fn render_to_string_matches_the_binary() {
+    let fixture = Fixture::new("render_to_string", &["empty/", "file.txt"]);
+    let mut settings = Settings::default();
+    settings
+        .path_container_settings
+        .number_of_directory_containers_to_print_option = Some(2);
+
+    let mut path_container = PathContainer::new(
+        fixture.get_path("empty"),
+        settings.path_container_settings,
+        settings.theme,
+        (120, 24),
+    );
+
+    assert_snapshot("empty_directory", &path_container.render_to_string(80));
+
+    // The text markers are only used while the styles are dropped
+    assert!(!path_container
+        .render()
+        .get_string(false)
+        .contains("* (D) empty"));
+}
+
+#[test]
+fn render_rows_of_directory_containers_within_the_terminal_height() {
+    let mut entry_path_vec: Vec<String> = Vec::new();
+    for directory_path in ["", "first/", "first/second/"].iter() {
+        for file_number in 1..=12 {
+            entry_path_vec.push(format!("{}file_{:02}.txt", directory_path, file_number));
+        }
+    }
+    let entry_path_str_vec: Vec<&str> = entry_path_vec.iter().map(String::as_str).collect();
+    let fixture = Fixture::new("height_of_rows", &entry_path_str_vec);
+    let mut settings = Settings::default();
+    settings
+        .path_container_settings
+        .number_of_directory_containers_to_print_option = Some(3);
+
+    let terminal_height = 30;
+    let mut path_container = PathContainer::new(
+        fixture.get_path("first/second"),
+        settings.path_container_settings,
+        settings.theme,
+        (40, terminal_height),
+    );
+    let rendered_path = path_container.render_to_string(40);
+
+    // The last line is left for the cursor
+    assert!(rendered_path.lines().count() < terminal_height);
+    assert_snapshot("height_of_rows", &rendered_path);
+}
+
+#[test]
+fn render_directory_containers_wider_than_the_terminal() {
+    let fixture = Fixture::new(
+        "too_wide",
+        &[
+            "a_file_name_that_is_much_too_long_to_fit_in_a_narrow_terminal.txt",
+            "a_directory_name_that_is_much_too_long_for_the_terminal/file.txt",
+            "short.txt",
+        ],
+    );
+    let no_truncation_settings_json = get_truncation_settings_json(r#""NoTruncation""#);
+
+    assert_snapshot(
+        "too_wide_truncated",
+        &fixture.render("", 30, &no_truncation_settings_json),
+    );
+    assert_snapshot(
+        "too_wide_clipped",
+        &fixture.render(
+            "a_directory_name_that_is_much_too_long_for_the_terminal",
+            30,
+            &no_truncation_settings_json,
+        ),
+    );
+}
+
+#[test]
+fn render_sort_settings() {
+    let fixture = Fixture::new(
+        "sort",
+        &[
+            "file10.txt",
+            "file9.txt",
+            "File1.md",
+            "archive.tar",
+            "README",
+            "src/",
+        ],
+    );
+
+    for (snapshot_name, sort_settings_json) in [
+        ("sort_name", r#"{ "sort_key_vec": ["Name"] }"#),
+        ("sort_natural_name", r#"{ "sort_key_vec": ["NaturalName"] }"#),
+        (
+            "sort_case_insensitive_name_directories_first",
+            r#"{ "sort_key_vec": ["CaseInsensitiveName"], "should_sort_directories_first": true }"#,
+        ),
+        (
+            "sort_extension_descending",
+            r#"{ "sort_key_vec": ["Extension", "NaturalName"], "should_sort_in_descending_order": true }"#,
+        ),
+    ]
+    .iter()
+    {
+        assert_snapshot(
+            snapshot_name,
+            &fixture.render(
+                "",
+                80,
+                &format!(
+                    r#"{{
+                        "path_container_settings": {{
+                            "directory_container_settings": {{ "sort_settings": {} }}
+                        }}
+                    }}"#,
+                    sort_settings_json
+                ),
+            ),
+        );
+    }
+}
+
+#[test]
+fn changing_settings_while_browsing_matches_the_settings_file() {
+    let fixture = Fixture::new(
+        "live",
+        &[
+            ".hidden_file",
+            ".hidden_directory/",
+            "visible_file",
+            "visible_directory/",
+        ],
+    );
+    let mut settings = Settings::default();
+    settings
+        .path_container_settings
+        .number_of_directory_containers_to_print_option = Some(1);
+
+    let mut path_container = PathContainer::new(
+        fixture.get_path(""),
+        settings.path_container_settings,
+        settings.theme,
+        (120, 24),
+    );
+
+    // The selected item stays selected as hidden files are displayed above it
+    path_container.toggle_hidden_files();
+    assert_snapshot(
+        "live_hidden_files_displayed",
+        &path_container.render_to_string(80),
+    );
+
+    path_container.reverse_sort_order();
+    path_container.reverse_sort_order();
+    path_container.toggle_hidden_files();
+    assert_snapshot("hidden_files_hidden", &path_container.render_to_string(80));
+
+    for _ in 0..8 {
+        path_container.cycle_sort_key();
+    }
+
+    assert_snapshot("hidden_files_hidden", &path_container.render_to_string(80));
+}
+
+#[test]
+fn render_status_line_while_cycling_sort_keys() {
+    let fixture = Fixture::new("status_line", &["b.txt", "a.md"]);
+    let mut settings = Settings::default();
+    settings
+        .path_container_settings
+        .number_of_directory_containers_to_print_option = Some(1);
+    settings
+        .path_container_settings
+        .directory_container_settings
+        .sort_settings
+        .sort_key_vec = vec![SortKey::Extension, SortKey::Name];
+
+    let mut path_container = PathContainer::new(
+        fixture.get_path(""),
+        settings.path_container_settings,
+        settings.theme,
+        (120, 24),
+    );
+    let get_status_line = |path_container: &PathContainer| {
+        let mut frame = path_container.render();
+        path_container.render_status_line(&mut frame);
+        frame.get_lines(false).last().unwrap().clone()
+    };
+
+    assert_eq!(
+        get_status_line(&path_container),
+        "[s] sort: extension, name  [r] order: ascending  [.] hidden files: hidden  \
+         [t] truncation: Outliers"
+    );
+
+    // Every sort key on its own, then back to the configured sort keys
+    let mut sort_key_names_vec = Vec::new();
+    for _ in 0..9 {
+        path_container.cycle_sort_key();
+        let status_line = get_status_line(&path_container);
+        sort_key_names_vec
+            .push(status_line["[s] sort: ".len()..status_line.find("  [r]").unwrap()].to_string());
+    }
+
+    assert_eq!(
+        sort_key_names_vec,
+        [
+            "name",
+            "natural name",
+            "case-insensitive name",
+            "extension",
+            "size",
+            "modification time",
+            "change time",
+            "item type",
+            "extension, name",
+        ]
+    );
+
+    path_container.reverse_sort_order();
+    path_container.toggle_hidden_files();
+    path_container.cycle_truncation_options();
+    assert_eq!(
+        get_status_line(&path_container),
+        "[s] sort: extension, name  [r] order: descending  [.] hidden files: shown  \
+         [t] truncation: AverageFileNameLength"
+    );
+
+    path_container.set_terminal_dimensions((60, 24));
+    assert_eq!(
+        get_status_line(&path_container),
+        "[s] sort: extension, name  [r] order: descending  [.] hid..."
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn render_symlinks() {
+    use std::os::unix::fs::symlink;
+
+    let fixture = Fixture::new("symlinks", &["target_directory/", "target_file.txt"]);
+
+    for (target_path, link_path) in [
+        ("target_directory", "link_to_directory"),
+        ("target_file.txt", "link_to_file"),
+        ("missing_file.txt", "broken_link"),
+    ]
+    .iter()
+    {
+        symlink(target_path, fixture.get_path(link_path)).unwrap();
+    }
+
+    assert_snapshot("symlinks", &fixture.render("", 80, "{}"));
+
+    // Targets are never truncated, so the directory container widens to fit them
+    assert_snapshot(
+        "symlinks_with_targets",
+        &fixture.render(
+            "",
+            80,
+            r#"{
+                "path_container_settings": {
+                    "directory_container_settings": {
+                        "truncation_options": {
+                            "Constant": {
+                                "constant": 8,
+                                "should_include_truncated_text_indicator_in_length": true
+                            }
+                        },
+                        "directory_item_settings": { "should_display_symlink_target": true }
+                    }
+                }
+            }"#,
+        ),
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn render_special_files() {
+    use std::{
+        fs::set_permissions,
+        os::unix::{fs::PermissionsExt, net::UnixListener},
+    };
+
+    let fixture = Fixture::new("special_files", &["executable.sh", "regular.txt"]);
+    set_permissions(
+        fixture.get_path("executable.sh"),
+        PermissionsExt::from_mode(0o755),
+    )
+    .unwrap();
+    assert!(Command::new("mkfifo")
+        .arg(fixture.get_path("named_pipe"))
+        .status()
+        .unwrap()
+        .success());
+    let _unix_listener = UnixListener::bind(fixture.get_path("socket")).unwrap();
+
+    assert_snapshot("special_files", &fixture.render("", 80, "{}"));
+}
+
+#[cfg(unix)]
+#[test]
+fn render_unreadable_directory() {
+    use std::{fs::set_permissions, os::unix::fs::PermissionsExt};
+
+    // Root can read the directory anyway
+    if uzers::get_current_uid() == 0 {
+        return;
+    }
+
+    let fixture = Fixture::new("unreadable", &["unreadable/", "readable.txt"]);
+    let unreadable_directory_path = fixture.get_path("unreadable");
+    set_permissions(&unreadable_directory_path, PermissionsExt::from_mode(0o000)).unwrap();
+
+    // The unreadable directory can't be the current directory, so it is passed as the path
+    let output = fixture
+        .get_command("", 80, "{}")
+        .arg("--color=never")
+        .arg("--containers=2")
+        .arg(&unreadable_directory_path)
+        .output()
+        .unwrap();
+    set_permissions(&unreadable_directory_path, PermissionsExt::from_mode(0o755)).unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_snapshot(
+        "unreadable_directory",
+        &String::from_utf8(output.stdout).unwrap(),
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn render_metadata_columns() {
+    use std::{fs::set_permissions, os::unix::fs::PermissionsExt};
+
+    let fixture = Fixture::new("metadata", &["empty.txt", "kibibytes.bin", "script.sh"]);
+
+    for (relative_path, size, mode) in [
+        ("empty.txt", 0, 0o644),
+        ("kibibytes.bin", 1536, 0o600),
+        ("script.sh", 20, 0o4755),
+    ]
+    .iter()
+    {
+        let path = fixture.get_path(relative_path);
+        write(&path, vec![b'x'; *size]).unwrap();
+        set_permissions(&path, PermissionsExt::from_mode(*mode)).unwrap();
+    }
+
+    for (snapshot_name, size_options_json) in [
+        ("metadata_columns_iec", r#""Iec""#),
+        ("metadata_columns_bytes", r#""Bytes""#),
+    ]
+    .iter()
+    {
+        assert_snapshot(
+            snapshot_name,
+            &fixture.render(
+                "",
+                80,
+                &format!(
+                    r#"{{
+                        "path_container_settings": {{
+                            "directory_container_settings": {{
+                                "metadata_column_settings": {{
+                                    "metadata_column_vec": ["Permissions", "LinkCount", "Size"],
+                                    "size_options": {}
+                                }}
+                            }}
+                        }}
+                    }}"#,
+                    size_options_json
+                ),
+            ),
+        );
+    }
+
+    // 2021-02-03 04:05:06 UTC, and a year later
+    for (relative_path, seconds_since_epoch) in [
+        ("empty.txt", 1_612_325_106),
+        ("kibibytes.bin", 1_643_861_106),
+        ("script.sh", 1_612_325_106),
+    ]
+    .iter()
+    {
+        File::options()
+            .write(true)
+            .open(fixture.get_path(relative_path))
+            .unwrap()
+            .set_modified(UNIX_EPOCH + Duration::from_secs(*seconds_since_epoch))
+            .unwrap();
+    }
+
+    let output = fixture
+        .get_command(
+            "",
+            80,
+            r#"{
+                "path_container_settings": {
+                    "directory_container_settings": {
+                        "metadata_column_settings": {
+                            "metadata_column_vec": ["ModificationTime"],
+                            "time_options": { "Absolute": { "time_format": "%Y-%m-%d %H:%M:%S" } }
+                        }
+                    }
+                }
+            }"#,
+        )
+        .arg("--color=never")
+        .env("TZ", "UTC")
+        .output()
+        .unwrap();
+    assert_snapshot(
+        "metadata_columns_modification_time",
+        &String::from_utf8(output.stdout).unwrap(),
+    );
+}
+
+#[test]
+fn render_git_status() {
+    let fixture = Fixture::new(
+        "git_status",
+        &[
+            ".gitignore",
+            "committed.txt",
+            "modified.txt",
+            "src/modified.rs",
+            "src/committed.rs",
+        ],
+    );
+    write(fixture.get_path(".gitignore"), "*.log\n").unwrap();
+
+    let repository = git2::Repository::init(fixture.get_path("")).unwrap();
+    let mut index = repository.index().unwrap();
+    for relative_path in [
+        ".gitignore",
+        "committed.txt",
+        "modified.txt",
+        "src/modified.rs",
+        "src/committed.rs",
+    ]
+    .iter()
+    {
+        index.add_path(Path::new(relative_path)).unwrap();
+    }
+    let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = git2::Signature::now("termifind", "termifind@example.com").unwrap();
+    repository
+        .commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "Add files",
+            &tree,
+            &[],
+        )
+        .unwrap();
+
+    write(fixture.get_path("modified.txt"), "changed").unwrap();
+    write(fixture.get_path("src/modified.rs"), "changed").unwrap();
+    write(fixture.get_path("staged.txt"), "").unwrap();
+    index.add_path(Path::new("staged.txt")).unwrap();
+    index.write().unwrap();
+    write(fixture.get_path("untracked.txt"), "").unwrap();
+    create_dir_all(fixture.get_path("untracked_directory")).unwrap();
+    write(fixture.get_path("untracked_directory/file.txt"), "").unwrap();
+    write(fixture.get_path("debug.log"), "").unwrap();
+
+    let git_status_settings_json = r#"{
+        "path_container_settings": {
+            "directory_container_settings": { "should_display_git_status": true }
+        }
+    }"#;
+
+    assert_snapshot(
+        "git_status",
+        &fixture.render("", 80, git_status_settings_json),
+    );
+    assert_snapshot(
+        "git_status_untracked_directory",
+        &fixture.render("untracked_directory", 80, git_status_settings_json),
+    );
+    assert_snapshot("git_status_disabled", &fixture.render("", 80, "{}"));
+}
+
+#[test]
+fn render_ignored_files() {
+    let fixture = Fixture::new(
+        "ignored_files",
+        &[
+            ".gitignore",
+            ".ignore",
+            "debug.log",
+            "global.txt",
+            "important.log",
+            "notes.txt",
+            "scratch.txt",
+            "src/main.rs",
+            "src/generated.rs",
+            "target/debug/termifind",
+        ],
+    );
+    git2::Repository::init(fixture.get_path("")).unwrap();
+    write(fixture.get_path(".gitignore"), "*.log\ntarget/\n").unwrap();
+    write(fixture.get_path(".ignore"), "!important.log\n").unwrap();
+    write(fixture.get_path("src/.gitignore"), "generated.rs\n").unwrap();
+    write(fixture.get_path(".git/info/exclude"), "scratch.txt\n").unwrap();
+    let global_excludes_path = fixture.get_user_configuration_path().join("git/ignore");
+    create_dir_all(global_excludes_path.parent().unwrap()).unwrap();
+    write(global_excludes_path, "global.txt\n").unwrap();
+
+    let settings = |ignored_file_options: &str| {
+        format!(
+            r#"{{
+                "path_container_settings": {{
+                    "directory_container_settings": {{
+                        "should_display_git_status": false,
+                        "should_display_hidden_files": true,
+                        "ignored_file_options": "{}"
+                    }}
+                }}
+            }}"#,
+            ignored_file_options
+        )
+    };
+
+    assert_snapshot(
+        "ignored_files_hidden",
+        &fixture.render("", 80, &settings("Hide")),
+    );
+    assert_snapshot(
+        "ignored_files_hidden_nested",
+        &fixture.render("src", 80, &settings("Hide")),
+    );
+    assert_snapshot(
+        "ignored_files_displayed",
+        &fixture.render("", 80, &settings("Display")),
+    );
+
+    // Without colors, dimmed files are marked with `ignored_file_text_marker` instead
+    assert_snapshot(
+        "ignored_files_dimmed",
+        &fixture.render("", 80, &settings("Dim")),
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn render_executables_with_ls_colors_falls_back_like_ls() {
+    use std::{fs::set_permissions, os::unix::fs::PermissionsExt};
+
+    let fixture = Fixture::new("ls_colors", &["a/", "script.sh", "tool"]);
+    for relative_path in ["script.sh", "tool"].iter() {
+        set_permissions(
+            fixture.get_path(relative_path),
+            PermissionsExt::from_mode(0o755),
+        )
+        .unwrap();
+    }
+
+    let output = fixture
+        .get_command("", 80, r#"{ "theme": { "should_use_ls_colors": true } }"#)
+        .arg("--color=always")
+        .env("LS_COLORS", "fi=33:*.sh=31")
+        .output()
+        .unwrap();
+    let rendered_path = String::from_utf8(output.stdout).unwrap();
+
+    // Without `ex`, executables are styled by their `*.ext` pattern, then by `fi`
+    assert!(rendered_path.contains("\u{1b}[31m(X) script.sh"));
+    assert!(rendered_path.contains("\u{1b}[33m(X) tool"));
+}